@@ -3,6 +3,7 @@ use std::error::Error;
 use utoipa::openapi::{ContactBuilder, InfoBuilder, ServerBuilder};
 
 use nano_rs::axum::generator::gen_api_info::AxumGenApiInfo;
+use nano_rs::axum::generator::gen_client::AxumGenClient;
 use nano_rs::axum::generator::gen_doc::AxumGenDoc;
 use nano_rs::axum::generator::gen_route::AxumGenRoute;
 use nano_rs::core::NanoBuilder;
@@ -40,6 +41,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 )
                 .build(),
         )
-        .gen_api_info(AxumGenApiInfo::new());
+        .gen_api_info(AxumGenApiInfo::new())
+        .gen_api_client(AxumGenClient::new());
     Ok(())
 }
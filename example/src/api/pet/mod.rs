@@ -19,7 +19,7 @@ pub mod samoyed {
             (status = 200, body = PetShower)
         )
     )]
-    #[post(layers = ["crate::layers::auth::auth_token#{crate::ServiceContext}", "crate::layers::auth::auth_token1"])]
+    #[post(layers = ["crate::layers::auth::auth_token#{crate::ServiceContext}", "crate::layers::auth::auth_token1"], roles = ["groomer", "admin"])]
     pub async fn shower(State(_svc): State<ServiceContext>) -> Result<RestResp<PetShower>, ServerError> {
         biz_ok!(PetShower {
             name: "mantou".to_string(),
@@ -36,7 +36,7 @@ pub mod samoyed {
             (status = 200, body = String)
         )
     )]
-    #[get()]
+    #[get(open = true)]
     pub async fn name(State(_svc): State<ServiceContext>) -> Result<RestResp<String>, ServerError> {
         biz_ok!("mantou".to_string())
     }
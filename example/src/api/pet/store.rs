@@ -1,13 +1,15 @@
-use axum::{Form, Json};
-use axum::extract::{Query, State};
+use axum::extract::State;
 
 use nano_rs::{biz_err, biz_ok, get, post};
 use nano_rs::axum::errors::ServerError;
-use nano_rs::axum::extractor::Path;
+use nano_rs::axum::extractor::{Form, Json, Path, Query, Valid};
+use nano_rs::axum::extractor::valid::ValidationErrors;
+use nano_rs::axum::page::{biz_page, PageQuery, PagedResp};
 use nano_rs::axum::rest::{RestResp};
 use nano_rs::config::rest::RestConfig;
 
 use crate::model::pet::{Meta, Page, Params, Pet, PetForm, QueryPet};
+use crate::types::errors::PetError;
 
 /// Get the default pet store name
 #[utoipa::path(
@@ -44,11 +46,12 @@ pub async fn get_store_tel(State(rest_config): State<RestConfig>) -> Result<Rest
     tag = "Store",
     request_body = Pet,
     responses(
-    (status = 200, body = Pet)
+    (status = 200, body = Pet),
+    (status = 422, body = ValidationErrors)
     )
 )]
-#[post()]
-pub async fn add_json_pet(State(_rest_config): State<RestConfig>, Json(pet): Json<Pet>) -> Result<RestResp<Pet>, ServerError> {
+#[post(permission = "pet:write")]
+pub async fn add_json_pet(State(_rest_config): State<RestConfig>, Valid(Json(pet)): Valid<Json<Pet>>) -> Result<RestResp<Pet>, ServerError> {
     biz_ok!(pet)
 }
 
@@ -77,8 +80,11 @@ pub async fn add_form_pet(State(_rest_config): State<RestConfig>, Form(_pet): Fo
         (status = 200, body = Pet)
     )
 )]
-#[get()]
+#[get(rate_limit = "20/s")]
 pub async fn get_pet_name(Path(params): Path<Params>) -> Result<RestResp<Pet>, ServerError> {
+    if params.id <= 0 {
+        Err(PetError::NotFound)?
+    }
     biz_ok!(Pet {
         id: params.id,
         name: "Doggy".to_string(),
@@ -91,18 +97,18 @@ pub async fn get_pet_name(Path(params): Path<Params>) -> Result<RestResp<Pet>, S
 
 /// Get pet list
 #[utoipa::path(
-    post,
-    path = "/store/pet/list/{page}/{count}",
+    get,
+    path = "/store/pet/list",
     tag = "Store",
-    params(Page),
+    params(PageQuery),
     responses(
-        (status = 200, body = [Pet])
+        (status = 200, body = PagedResp<Pet>)
     )
 )]
-#[post()]
-pub async fn pet_page_list(State(_rest_config): State<RestConfig>, Path(_page): Path<Page>) -> Result<RestResp<Vec<Pet>>,
+#[get()]
+pub async fn pet_page_list(State(_rest_config): State<RestConfig>, page: PageQuery) -> Result<RestResp<PagedResp<Pet>>,
     ServerError> {
-    biz_ok!(vec![])
+    biz_page(vec![], 0, &page)
 }
 
 /// Get pet list by id
@@ -11,6 +11,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "hello".to_string(),
             summary: " Say Hello to name".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Samoyed".to_string(),
         },
         ApiInfo {
@@ -20,6 +22,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "miss".to_string(),
             summary: " Miss mantou so much".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Samoyed".to_string(),
         },
         ApiInfo {
@@ -28,7 +32,9 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             base_path: "".to_string(),
             handler_fun: "name".to_string(),
             summary: " Get Samoyed name".to_string(),
-            public: false,
+            public: true,
+            permission: None,
+            roles: vec![],
             group_name: "Samoyed".to_string(),
         },
         ApiInfo {
@@ -38,6 +44,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "shower".to_string(),
             summary: " Give your Samoyed a bath".to_string(),
             public: false,
+            permission: None,
+            roles: vec!["groomer".to_string(), "admin".to_string()],
             group_name: "Samoyed".to_string(),
         },
         ApiInfo {
@@ -47,6 +55,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "add_form_pet".to_string(),
             summary: " Add a new pet to the store(form)".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
@@ -56,6 +66,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "add_json_pet".to_string(),
             summary: " Add a new pet to the store(json)".to_string(),
             public: false,
+            permission: Some("pet:write".to_string()),
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
@@ -65,6 +77,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "get_pet_name".to_string(),
             summary: " Get pet by id".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
@@ -74,6 +88,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "get_pet_name_list".to_string(),
             summary: " Get pet list by id".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
@@ -83,6 +99,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "get_query_pet_name".to_string(),
             summary: " Query pet by id".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
@@ -92,6 +110,8 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "get_store_name".to_string(),
             summary: " Get the default pet store name".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
@@ -101,15 +121,19 @@ pub fn get_api_info() -> Vec<ApiInfo> {
             handler_fun: "get_store_tel".to_string(),
             summary: " Get Store's telephone number".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
         ApiInfo {
-            method: "post".to_string(),
-            path: "/store/pet/list/{page}/{count}".to_string(),
+            method: "get".to_string(),
+            path: "/store/pet/list".to_string(),
             base_path: "".to_string(),
             handler_fun: "pet_page_list".to_string(),
             summary: " Get pet list".to_string(),
             public: false,
+            permission: None,
+            roles: vec![],
             group_name: "Store".to_string(),
         },
     ]
@@ -0,0 +1,108 @@
+/// Code generated by nano-rs. DO NOT EDIT.
+use axum::http::Method;
+use nano_rs::axum::client::{ClientError, ClientRequest, RestClient};
+use nano_rs::axum::rest::RestResp;
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct ApiClient {
+    pub client: RestClient,
+}
+#[allow(dead_code)]
+impl ApiClient {
+    pub fn new(client: RestClient) -> Self {
+        ApiClient { client }
+    }
+    /// Say Hello to name
+    /// `GET /samoyed/{name}`
+    pub async fn hello(&self, name: &str) -> Result<RestResp<String>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/samoyed/{name}").path(name)?;
+        self.client.send(req).await
+    }
+    /// Miss mantou so much
+    /// `GET /samoyed/miss`
+    pub async fn miss(&self) -> Result<RestResp<String>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/samoyed/miss");
+        self.client.send(req).await
+    }
+    /// Get Samoyed name
+    /// `GET /samoyed/name`
+    pub async fn name(&self) -> Result<RestResp<String>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/samoyed/name");
+        self.client.send(req).await
+    }
+    /// Give your Samoyed a bath
+    /// `POST /samoyed/shower`
+    pub async fn shower(&self) -> Result<RestResp<crate::types::pet::PetShower>, ClientError> {
+        let req = ClientRequest::new(Method::POST, "/samoyed/shower");
+        self.client.send(req).await
+    }
+    /// Add a new pet to the store(form)
+    /// `POST /store/pet/form`
+    pub async fn add_form_pet(
+        &self,
+        pet: &crate::model::pet::PetForm,
+    ) -> Result<RestResp<crate::model::pet::Pet>, ClientError> {
+        let req = ClientRequest::new(Method::POST, "/store/pet/form").form(pet)?;
+        self.client.send(req).await
+    }
+    /// Add a new pet to the store(json)
+    /// `POST /store/pet/json`
+    pub async fn add_json_pet(
+        &self,
+        body: &crate::model::pet::Pet,
+    ) -> Result<RestResp<crate::model::pet::Pet>, ClientError> {
+        let req = ClientRequest::new(Method::POST, "/store/pet/json").json(body)?;
+        self.client.send(req).await
+    }
+    /// Get pet by id
+    /// `GET /store/pet/{id}`
+    pub async fn get_pet_name(
+        &self,
+        params: &crate::model::pet::Params,
+    ) -> Result<RestResp<crate::model::pet::Pet>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/store/pet/{id}").path(params)?;
+        self.client.send(req).await
+    }
+    /// Get pet list by id
+    /// `GET /store/pet/list/{page}/{count}/{id}`
+    pub async fn get_pet_name_list(
+        &self,
+        page: &crate::model::pet::Page,
+        params: &crate::model::pet::Params,
+    ) -> Result<RestResp<crate::model::pet::Pet>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/store/pet/list/{page}/{count}/{id}")
+            .path(page)?
+            .path(params)?;
+        self.client.send(req).await
+    }
+    /// Query pet by id
+    /// `GET /store/pet`
+    pub async fn get_query_pet_name(
+        &self,
+        query: &crate::model::pet::QueryPet,
+    ) -> Result<RestResp<crate::model::pet::Pet>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/store/pet").query(query)?;
+        self.client.send(req).await
+    }
+    /// Get the default pet store name
+    /// `GET /store/name`
+    pub async fn get_store_name(&self) -> Result<RestResp<String>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/store/name");
+        self.client.send(req).await
+    }
+    /// Get Store's telephone number
+    /// `GET /store/tel`
+    pub async fn get_store_tel(&self) -> Result<RestResp<String>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/store/tel");
+        self.client.send(req).await
+    }
+    /// Get pet list
+    /// `GET /store/pet/list`
+    pub async fn pet_page_list(
+        &self,
+        page: &nano_rs::axum::page::PageQuery,
+    ) -> Result<RestResp<nano_rs::axum::page::PagedResp<crate::model::pet::Pet>>, ClientError> {
+        let req = ClientRequest::new(Method::GET, "/store/pet/list").query(page)?;
+        self.client.send(req).await
+    }
+}
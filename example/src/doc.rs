@@ -39,3 +39,14 @@ use utoipa::OpenApi;
     tags()
 )]
 pub struct GenApi {}
+impl GenApi {
+    /// openapi of the routes as served with `rest_config` (base path, envelope and
+    /// response format), prefer it over `GenApi::openapi()`
+    pub fn openapi_with_config(
+        rest_config: &nano_rs::config::rest::RestConfig,
+    ) -> utoipa::openapi::OpenApi {
+        let mut openapi = <GenApi as OpenApi>::openapi();
+        nano_rs::axum::generator::gen_doc::apply_rest_config(&mut openapi, rest_config);
+        openapi
+    }
+}
@@ -15,6 +15,7 @@ pub async fn auth_token(State(_svc): State<ServiceContext>,request: Request, nex
             code: 502,
             msg: "auth server not found, please try again later".to_string(),
             data: None,
+            status: None,
         });
     }
 }
@@ -28,6 +29,7 @@ pub async fn auth_token1(request: Request, next: Next) -> Result<Response, impl
             code: 502,
             msg: "auth server not found, please try again later".to_string(),
             data: None,
+            status: None,
         });
     }
 }
\ No newline at end of file
@@ -1,17 +1,21 @@
 use axum::Router;
 use axum_client_ip::ClientIpSource;
+use nano_rs::axum::errors::BizError;
+use nano_rs::axum::generator::gen_doc::apply_biz_errors;
+use nano_rs::axum::health::HealthChecks;
 use nano_rs::axum::start::AppStarter;
 use nano_rs::config::init_config_with_cli;
 use nano_rs::config::rest::RestConfig;
-use tower_http::cors::{Any, CorsLayer};
-use utoipa::OpenApi;
 use utoipa_rapidoc::RapiDoc;
 
+use crate::api_info::get_api_info;
 use crate::doc::GenApi;
 use crate::routes::get_routes;
+use crate::types::errors::PetError;
 
 mod api;
 mod api_info;
+mod client;
 mod doc;
 mod layers;
 mod model;
@@ -26,21 +30,17 @@ async fn main() {
         rest_config: rest_config.clone(),
     };
 
-    let app = Router::new()
-        .merge(RapiDoc::with_openapi("/api-docs/openapi2.json", GenApi::openapi()).path("/doc"));
-    let app = if rest_config.base_path.as_str().is_empty() || rest_config.base_path.as_str().eq("/")
-    {
-        app.merge(get_routes(service_context.clone(), rest_config.clone()))
-    } else {
-        app.nest(
-            rest_config.base_path.as_str(),
-            get_routes(service_context.clone(), rest_config.clone()),
-        )
-    };
-    let app = app.layer(CorsLayer::new().allow_origin(Any).allow_methods(Any));
+    let mut openapi = GenApi::openapi_with_config(&rest_config);
+    apply_biz_errors(&mut openapi, &PetError::all());
+    let app =
+        Router::new().merge(RapiDoc::with_openapi("/api-docs/openapi2.json", openapi).path("/doc"));
     AppStarter::new(app, rest_config.clone())
+        .with_routes(get_routes(service_context.clone(), rest_config.clone()))
         .add_log_layer_with_config(Some(rest_config.log))
         .add_secure_client_ip_source_layer(ClientIpSource::ConnectInfo)
+        .add_prometheus_layer()
+        .add_health_routes(HealthChecks::new())
+        .add_api_info(get_api_info())
         .run()
         .await;
 }
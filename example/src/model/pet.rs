@@ -1,3 +1,4 @@
+use nano_rs::axum::extractor::valid::{Validate, ValidationErrors};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
@@ -16,6 +17,16 @@ pub struct Pet {
     pub meta: Meta,
 }
 
+impl Validate for Pet {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        ValidationErrors::new()
+            .check(self.id > 0, "id", "must be positive")
+            .check(!self.name.trim().is_empty(), "name", "must not be empty")
+            .nested("meta", self.meta.validate())
+            .into_result()
+    }
+}
+
 /// Pet Data
 #[derive(Deserialize, Serialize, Debug, ToSchema)]
 pub struct PetForm {
@@ -42,7 +53,15 @@ pub struct Meta {
     pub age: i64,
 }
 
-#[derive(Deserialize, IntoParams)]
+impl Validate for Meta {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        ValidationErrors::new()
+            .check((0..=30).contains(&self.age), "age", "must be between 0 and 30")
+            .into_result()
+    }
+}
+
+#[derive(Deserialize, Serialize, IntoParams)]
 #[into_params(style = Form, parameter_in = Query)]
 pub struct QueryPet {
     /// The unique identifier for a pet
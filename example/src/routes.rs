@@ -30,14 +30,32 @@ pub fn get_routes(
 pub fn get_routes_crate_service_context(crate_service_context: crate::ServiceContext) -> Router {
     Router::new()
         .route("/samoyed/name", get(crate::api::pet::samoyed::name))
-        .route("/samoyed/{name}", get(crate::api::pet::samoyed::hello))
+        .route(
+            "/samoyed/{name}",
+            get(crate::api::pet::samoyed::hello).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
+        )
         .with_state(crate_service_context)
 }
 pub fn get_routes_crate_service_context_with_layer_crate_layers_auth_auth_token_crate_service_context_with_layer_crate_layers_auth_auth_token1(
     crate_service_context: crate::ServiceContext,
 ) -> Router {
     Router::new()
-        .route("/samoyed/shower", post(crate::api::pet::samoyed::shower))
+        .route(
+            "/samoyed/shower",
+            post(crate::api::pet::samoyed::shower)
+                .route_layer(axum::middleware::from_fn_with_state(
+                    nano_rs::axum::middleware::permission::Requirement::new(
+                        None,
+                        &["groomer", "admin"],
+                    ),
+                    nano_rs::axum::middleware::permission::require_permission,
+                ))
+                .route_layer(axum::middleware::from_fn(
+                    nano_rs::axum::middleware::auth::require_auth,
+                )),
+        )
         .route_layer(axum::middleware::from_fn_with_state(
             crate_service_context.clone(),
             crate::layers::auth::auth_token,
@@ -51,15 +69,26 @@ pub fn get_routes_nano_rs_config_rest_rest_config(
     Router::new()
         .route(
             "/store/pet/form",
-            post(crate::api::pet::store::add_form_pet),
+            post(crate::api::pet::store::add_form_pet).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
         )
         .route(
             "/store/pet/json",
-            post(crate::api::pet::store::add_json_pet),
+            post(crate::api::pet::store::add_json_pet)
+                .route_layer(axum::middleware::from_fn_with_state(
+                    nano_rs::axum::middleware::permission::Requirement::new(Some("pet:write"), &[]),
+                    nano_rs::axum::middleware::permission::require_permission,
+                ))
+                .route_layer(axum::middleware::from_fn(
+                    nano_rs::axum::middleware::auth::require_auth,
+                )),
         )
         .route(
-            "/store/pet/list/{page}/{count}",
-            post(crate::api::pet::store::pet_page_list),
+            "/store/pet/list",
+            get(crate::api::pet::store::pet_page_list).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
         )
         .with_state(nano_rs_config_rest_rest_config)
 }
@@ -68,7 +97,12 @@ pub fn get_routes_nano_rs_config_rest_rest_config_with_layer_crate_layers_auth_a
     crate_service_context: crate::ServiceContext,
 ) -> Router {
     Router::new()
-        .route("/store/tel", get(crate::api::pet::store::get_store_tel))
+        .route(
+            "/store/tel",
+            get(crate::api::pet::store::get_store_tel).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
+        )
         .route_layer(axum::middleware::from_fn_with_state(
             crate_service_context,
             crate::layers::auth::auth_token,
@@ -77,19 +111,46 @@ pub fn get_routes_nano_rs_config_rest_rest_config_with_layer_crate_layers_auth_a
 }
 pub fn get_routes_without_state() -> Router {
     Router::new()
-        .route("/samoyed/miss", get(crate::api::pet::samoyed::miss))
+        .route(
+            "/samoyed/miss",
+            get(crate::api::pet::samoyed::miss).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
+        )
         .route(
             "/store/pet",
-            get(crate::api::pet::store::get_query_pet_name),
+            get(crate::api::pet::store::get_query_pet_name).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
         )
         .route(
             "/store/pet/list/{page}/{count}/{id}",
-            get(crate::api::pet::store::get_pet_name_list),
+            get(crate::api::pet::store::get_pet_name_list).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
+        )
+        .route(
+            "/store/pet/{id}",
+            get(crate::api::pet::store::get_pet_name)
+                .route_layer(axum::middleware::from_fn(
+                    nano_rs::axum::middleware::auth::require_auth,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    nano_rs::axum::middleware::rate_limit::RateLimiter::per_route(
+                        "20/s",
+                        "GET /store/pet/{id}",
+                    ),
+                    nano_rs::axum::middleware::rate_limit::rate_limit,
+                )),
         )
-        .route("/store/pet/{id}", get(crate::api::pet::store::get_pet_name))
 }
 pub fn get_routes_without_state_with_layer_crate_layers_auth_auth_token1() -> Router {
     Router::new()
-        .route("/store/name", get(crate::api::pet::store::get_store_name))
+        .route(
+            "/store/name",
+            get(crate::api::pet::store::get_store_name).route_layer(axum::middleware::from_fn(
+                nano_rs::axum::middleware::auth::require_auth,
+            )),
+        )
         .route_layer(axum::middleware::from_fn(crate::layers::auth::auth_token1))
 }
@@ -0,0 +1,9 @@
+use nano_rs::BizError;
+
+#[derive(Debug, BizError)]
+pub enum PetError {
+    #[biz_error(code = 40001, msg = "pet not found", status = 404, key = "pet.not_found")]
+    NotFound,
+    #[biz_error(code = 40002, msg = "pet name is taken", key = "pet.name_taken")]
+    NameTaken(String),
+}
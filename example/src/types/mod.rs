@@ -1 +1,2 @@
+pub mod errors;
 pub mod pet;
\ No newline at end of file
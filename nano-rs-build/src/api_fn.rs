@@ -20,6 +20,8 @@ pub struct ApiFn<L, I, U, A> {
     pub layers_fn_name: Option<Vec<L>>,
     /// api input
     pub inputs: Option<I>,
+    /// api return type tokens, e.g. "Result < RestResp < Pet > , ServerError >"
+    pub output: Option<String>,
     /// route path
     pub path: String,
     /// route group path
@@ -28,6 +30,12 @@ pub struct ApiFn<L, I, U, A> {
     pub method: String,
     /// is need auth
     pub public: bool,
+    /// route rate limit, e.g. "100/min"
+    pub rate_limit: Option<String>,
+    /// permission required by the route, e.g. "pet:write"
+    pub permission: Option<String>,
+    /// roles allowed to call the route, any of them
+    pub roles: Option<Vec<String>>,
     /// api function doc
     pub api_fn_doc: Option<ApiFnDoc>,
     /// use crate
@@ -36,9 +44,15 @@ pub struct ApiFn<L, I, U, A> {
     pub attrs: Option<A>,
 }
 
+/// api fn parsed from the source by syn
+pub type SynApiFn = ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>;
+
+/// parsed api fns by handler path (e.g. "crate::api::pet::store::get_store_name"), input of the generators
+pub type ApiFns = HashMap<String, SynApiFn>;
+
 pub fn get_rs_files_fns(
     files: &mut Vec<PathBuf>,
-) -> Result<HashMap<String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>>, Box<dyn Error>> {
+) -> Result<ApiFns, Box<dyn Error>> {
     let mut fns = HashMap::new();
     for file in files {
         // 读入你的 Rust 源文件
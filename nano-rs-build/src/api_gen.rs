@@ -1,13 +1,9 @@
-use std::collections::HashMap;
 use std::path::PathBuf;
-use syn::{Attribute, FnArg, ItemUse};
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
-use crate::api_fn::ApiFn;
+use crate::api_fn::ApiFns;
 
 /// GenRoute trait
 pub trait GenRoute {
-    fn gen_route(&self, rs_files: Vec<PathBuf>, path_buf: PathBuf, api_fns: HashMap<String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>>);
+    fn gen_route(&self, rs_files: Vec<PathBuf>, path_buf: PathBuf, api_fns: ApiFns);
 
     fn get_routes_file_path(&self) -> &'static str {
         "src/routes.rs"
@@ -16,7 +12,7 @@ pub trait GenRoute {
 
 /// GenDoc trait
 pub trait GenDoc {
-    fn gen_doc(&self, rs_files: Vec<PathBuf>, path_buf: PathBuf, api_fns: HashMap<String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>>);
+    fn gen_doc(&self, rs_files: Vec<PathBuf>, path_buf: PathBuf, api_fns: ApiFns);
 
     fn get_doc_file_path(&self) -> &'static str {
         "src/doc.rs"
@@ -25,9 +21,18 @@ pub trait GenDoc {
 
 /// GenApiInfo trait
 pub trait GenApiInfo {
-    fn gen_api_info(&self, path_buf: PathBuf, api_fns: HashMap<String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>>);
+    fn gen_api_info(&self, path_buf: PathBuf, api_fns: ApiFns);
 
     fn get_api_info_file_path(&self) -> &'static str {
         "src/api_info.rs"
     }
-}
\ No newline at end of file
+}
+
+/// GenClient trait
+pub trait GenClient {
+    fn gen_client(&self, path_buf: PathBuf, api_fns: ApiFns);
+
+    fn get_client_file_path(&self) -> &'static str {
+        "src/client.rs"
+    }
+}
@@ -1,14 +1,12 @@
-use std::collections::HashMap;
 use std::error::Error;
 use std::ops::Add;
 use std::path::PathBuf;
 
-use syn::{Attribute, Expr, FnArg, Item, ItemFn, ItemMod, ItemUse, Lit, Meta};
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
+use quote::ToTokens;
+use syn::{Attribute, Expr, Item, ItemFn, ItemMod, ItemUse, Lit, Meta, ReturnType};
 
 use crate::api_doc::ApiFnDoc;
-use crate::api_fn::ApiFn;
+use crate::api_fn::{ApiFn, ApiFns, SynApiFn};
 use crate::api_syn::ApiMacroInfo;
 
 pub fn gen_fn_full_crate_path(path_buf: &PathBuf, fn_name: String, mod_name: Option<&str>) -> String {
@@ -67,7 +65,7 @@ pub fn extract_doc_comments(attrs: &Vec<Attribute>) -> Vec<String> {
         .collect()
 }
 
-pub fn parse_fn_item_in_mod(fns: &mut HashMap<String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>>, item_mod: &ItemMod, mod_name: &str, path_buf: PathBuf) {
+pub fn parse_fn_item_in_mod(fns: &mut ApiFns, item_mod: &ItemMod, mod_name: &str, path_buf: PathBuf) {
     //先获取全部的use,防止有些文件没有进行rustfmt
     let mut item_uses: Vec<ItemUse> = vec![];
     for content in item_mod.content.iter() {
@@ -107,7 +105,7 @@ pub fn parse_fn_item_in_mod(fns: &mut HashMap<String, ApiFn<String, Punctuated<F
     }
 }
 
-pub fn parse_fn_item(item_fn: &ItemFn, path_buf: PathBuf, mod_name: Option<&str>) -> Result<Option<(String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>)>, Box<dyn Error>> {
+pub fn parse_fn_item(item_fn: &ItemFn, path_buf: PathBuf, mod_name: Option<&str>) -> Result<Option<(String, SynApiFn)>, Box<dyn Error>> {
     //获取函数上的标记宏
     const METHODS: [&str; 8] = ["post", "get", "put", "delete", "patch", "options", "head", "trace"];
     for attr in &item_fn.attrs {
@@ -128,7 +126,7 @@ pub fn parse_fn_item(item_fn: &ItemFn, path_buf: PathBuf, mod_name: Option<&str>
     Ok(None)
 }
 
-pub fn parse_api_info(item_fn: &ItemFn, attr: &Attribute, method: &str) -> Result<ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>, Box<dyn Error>> {
+pub fn parse_api_info(item_fn: &ItemFn, attr: &Attribute, method: &str) -> Result<SynApiFn, Box<dyn Error>> {
     let api_macro_info = attr.parse_args::<ApiMacroInfo>()?;
     let open_token = if let Some(open) = api_macro_info.open_token {
         open.value_token.value
@@ -142,6 +140,10 @@ pub fn parse_api_info(item_fn: &ItemFn, attr: &Attribute, method: &str) -> Resul
             layers.push(path_str);
         });
     }
+    let roles: Vec<String> = api_macro_info
+        .roles_token
+        .map(|roles_token| roles_token.value_token.iter().map(|role| role.value()).collect())
+        .unwrap_or_default();
     // extract doc comments
     let docs = extract_doc_comments(&item_fn.attrs);
 
@@ -153,6 +155,10 @@ pub fn parse_api_info(item_fn: &ItemFn, attr: &Attribute, method: &str) -> Resul
             Some(layers)
         },
         inputs: Some(item_fn.sig.inputs.clone()),
+        output: match &item_fn.sig.output {
+            ReturnType::Type(_, output) => Some(output.to_token_stream().to_string()),
+            ReturnType::Default => None,
+        },
         path: if let Some(path) = api_macro_info.path_token {
             path.value_token.value()
         } else {
@@ -165,6 +171,17 @@ pub fn parse_api_info(item_fn: &ItemFn, attr: &Attribute, method: &str) -> Resul
         },
         method: method.to_string(),
         public: open_token,
+        rate_limit: api_macro_info
+            .rate_limit_token
+            .map(|rate_limit| rate_limit.value_token.value()),
+        permission: api_macro_info
+            .permission_token
+            .map(|permission| permission.value_token.value()),
+        roles: if roles.is_empty() {
+            None
+        } else {
+            Some(roles)
+        },
         api_fn_doc: Some(ApiFnDoc {
             api: if api_macro_info.api_token.is_none() {
                 if let Some(summary) = docs.first() {
@@ -9,6 +9,9 @@ pub mod api_key_word {
     syn::custom_keyword!(api);
     syn::custom_keyword!(open);
     syn::custom_keyword!(path_group);
+    syn::custom_keyword!(rate_limit);
+    syn::custom_keyword!(permission);
+    syn::custom_keyword!(roles);
 }
 
 pub struct ApiMacroInfo {
@@ -18,6 +21,9 @@ pub struct ApiMacroInfo {
     pub group_token: Option<GroupToken>,
     pub api_token: Option<ApiToken>,
     pub open_token: Option<OpenToken>,
+    pub rate_limit_token: Option<RateLimitToken>,
+    pub permission_token: Option<PermissionToken>,
+    pub roles_token: Option<RolesToken>,
 }
 
 impl Parse for ApiMacroInfo {
@@ -28,6 +34,9 @@ impl Parse for ApiMacroInfo {
         let mut group_token = None;
         let mut api_token = None;
         let mut open_token = None;
+        let mut rate_limit_token = None;
+        let mut permission_token = None;
+        let mut roles_token = None;
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(api_key_word::path) {
@@ -60,6 +69,21 @@ impl Parse for ApiMacroInfo {
                     return Err(input.error("Duplicate 'open' keyword"));
                 }
                 open_token = Some(input.parse::<OpenToken>()?);
+            } else if lookahead.peek(api_key_word::rate_limit) {
+                if rate_limit_token.is_some() {
+                    return Err(input.error("Duplicate 'rate_limit' keyword"));
+                }
+                rate_limit_token = Some(input.parse::<RateLimitToken>()?);
+            } else if lookahead.peek(api_key_word::permission) {
+                if permission_token.is_some() {
+                    return Err(input.error("Duplicate 'permission' keyword"));
+                }
+                permission_token = Some(input.parse::<PermissionToken>()?);
+            } else if lookahead.peek(api_key_word::roles) {
+                if roles_token.is_some() {
+                    return Err(input.error("Duplicate 'roles' keyword"));
+                }
+                roles_token = Some(input.parse::<RolesToken>()?);
             } else {
                 // 否则不处理
             }
@@ -77,6 +101,9 @@ impl Parse for ApiMacroInfo {
             group_token,
             api_token,
             open_token,
+            rate_limit_token,
+            permission_token,
+            roles_token,
         })
     }
 }
@@ -181,4 +208,58 @@ impl Parse for PathGroupToken {
             value_token: input.parse()?,
         })
     }
+}
+
+pub struct RateLimitToken {
+    pub rate_limit_token: api_key_word::rate_limit,
+    pub eq_token: Token![=],
+    pub value_token: LitStr,
+}
+
+impl Parse for RateLimitToken {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(RateLimitToken {
+            rate_limit_token: input.parse::<api_key_word::rate_limit>()?,
+            eq_token: input.parse()?,
+            value_token: input.parse()?,
+        })
+    }
+}
+
+pub struct PermissionToken {
+    pub permission_token: api_key_word::permission,
+    pub eq_token: Token![=],
+    pub value_token: LitStr,
+}
+
+impl Parse for PermissionToken {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(PermissionToken {
+            permission_token: input.parse::<api_key_word::permission>()?,
+            eq_token: input.parse()?,
+            value_token: input.parse()?,
+        })
+    }
+}
+
+pub struct RolesToken {
+    pub roles_token: api_key_word::roles,
+    pub eq_token: Token![=],
+    pub value_token: Punctuated<LitStr, Token![,]>,
+}
+
+impl Parse for RolesToken {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let roles = input.parse::<api_key_word::roles>()?;
+        let eq_token = input.parse::<Token![=]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let value_token = content.parse_terminated(|input: ParseStream| input.parse::<LitStr>(), Token![,])?;
+
+        Ok(RolesToken {
+            roles_token: roles,
+            eq_token,
+            value_token,
+        })
+    }
 }
\ No newline at end of file
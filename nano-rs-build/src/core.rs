@@ -1,17 +1,12 @@
-use std::collections::HashMap;
 use std::path::PathBuf;
 
-use syn::{Attribute, FnArg, ItemUse};
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
-
 use crate::api_file::get_rs_files;
-use crate::api_fn::{ApiFn, get_rs_files_fns};
-use crate::api_gen::{GenApiInfo, GenDoc, GenRoute};
+use crate::api_fn::{ApiFns, get_rs_files_fns};
+use crate::api_gen::{GenApiInfo, GenClient, GenDoc, GenRoute};
 
 #[derive(Clone)]
 pub struct NanoBuilder {
-    api_fns: HashMap<String, ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>>,
+    api_fns: ApiFns,
     api_gen_path: PathBuf,
     rs_files: Vec<PathBuf>,
 }
@@ -50,5 +45,9 @@ impl NanoBuilder {
         gen_api_info.gen_api_info(self.clone().api_gen_path, self.api_fns.clone());
         self
     }
-}
 
+    pub fn gen_api_client(&mut self, gen_client: impl GenClient) -> &mut Self {
+        gen_client.gen_client(self.clone().api_gen_path, self.api_fns.clone());
+        self
+    }
+}
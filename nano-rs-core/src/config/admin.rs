@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// admin listener config, serves operational endpoints (metrics, health, log level, config, routes)
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct AdminConfig {
+    /// enable admin listener, default true
+    pub enable: Option<bool>,
+    /// admin listening address, default 127.0.0.1
+    pub host: Option<String>,
+    /// admin port
+    pub port: u16,
+}
+
+impl AdminConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    pub fn get_address(&self) -> String {
+        format!(
+            "{}:{}",
+            self.host.clone().unwrap_or("127.0.0.1".to_string()),
+            self.port
+        )
+    }
+}
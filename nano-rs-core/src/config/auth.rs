@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// jwt authentication of the generated routes, routes with `open = true` skip it
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct AuthConfig {
+    /// enable authentication, default true; false lets every request through the generated routes
+    pub enable: Option<bool>,
+    /// HS256 (default), HS384, HS512, RS256, RS384 or RS512
+    pub algorithm: Option<String>,
+    /// shared secret of HS algorithms
+    pub secret: Option<String>,
+    /// public key pem file of RS algorithms (pkcs1 or pkcs8)
+    pub public_key_file: Option<String>,
+    /// expected `iss` claim
+    pub issuer: Option<String>,
+    /// expected `aud` claim
+    pub audience: Option<String>,
+    /// allowed clock skew of `exp` and `nbf` (second), default 60
+    pub leeway: Option<u64>,
+}
+
+impl AuthConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    pub fn get_algorithm(&self) -> String {
+        self.algorithm.clone().unwrap_or("HS256".to_string())
+    }
+
+    pub fn get_leeway(&self) -> u64 {
+        self.leeway.unwrap_or(60)
+    }
+}
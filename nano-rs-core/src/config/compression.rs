@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// response compression and request decompression config
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct CompressionConfig {
+    /// enable compression, default true
+    pub enable: Option<bool>,
+    /// gzip, br, zstd, deflate, default gzip, br and zstd
+    pub algorithms: Option<Vec<String>>,
+    /// minimum response size (byte) to compress, default 1024
+    pub min_size: Option<u16>,
+    /// content type prefixes never compressed, images, grpc and sse are always excluded
+    pub exclude_content_types: Option<Vec<String>>,
+    /// decompress request bodies sent with `Content-Encoding`, default true
+    pub decompress_request: Option<bool>,
+}
+
+impl CompressionConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    pub fn is_decompress_request(&self) -> bool {
+        self.decompress_request.unwrap_or(true)
+    }
+
+    /// enabled algorithms, lowercase
+    pub fn get_algorithms(&self) -> Vec<String> {
+        match &self.algorithms {
+            Some(algorithms) => algorithms.iter().map(|a| a.trim().to_lowercase()).collect(),
+            None => vec!["gzip".to_string(), "br".to_string(), "zstd".to_string()],
+        }
+    }
+}
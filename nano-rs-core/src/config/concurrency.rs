@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// concurrency limit config, caps in-flight requests of the whole app
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct ConcurrencyConfig {
+    /// enable concurrency limit, default true
+    pub enable: Option<bool>,
+    /// max in-flight requests
+    pub max_concurrent: usize,
+    /// reject with 503 when saturated instead of queueing, default true
+    pub load_shed: Option<bool>,
+}
+
+impl ConcurrencyConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    pub fn is_load_shed(&self) -> bool {
+        self.load_shed.unwrap_or(true)
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// cors config, applied by `AppStarter` when present
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct CorsConfig {
+    /// allowed origins, "*" for any, supports wildcards like "https://*.example.com"
+    pub allow_origins: Option<Vec<String>>,
+    /// allowed methods, "*" for any, default GET,POST,PUT,DELETE,PATCH,OPTIONS,HEAD
+    pub allow_methods: Option<Vec<String>>,
+    /// allowed request headers, "*" for any
+    pub allow_headers: Option<Vec<String>>,
+    /// exposed response headers, "*" for any
+    pub expose_headers: Option<Vec<String>>,
+    /// allow credentials, default false, needs explicit origins/methods/headers ("*" is rejected)
+    pub allow_credentials: Option<bool>,
+    /// preflight cache max age (second)
+    pub max_age: Option<u64>,
+}
+
+impl CorsConfig {
+    /// allow everything (dev mode)
+    pub fn permissive() -> Self {
+        CorsConfig {
+            allow_origins: Some(vec!["*".to_string()]),
+            allow_methods: Some(vec!["*".to_string()]),
+            allow_headers: Some(vec!["*".to_string()]),
+            expose_headers: None,
+            allow_credentials: None,
+            max_age: None,
+        }
+    }
+
+    /// - 校验配置, 携带凭证时不允许使用 "*"
+    /// - Validate the config, "*" is rejected with `allow_credentials`, list origins or patterns
+    ///
+    /// #Example
+    /// ```rust
+    /// use nano_rs_core::config::cors::CorsConfig;
+    ///
+    /// let mut cors = CorsConfig::permissive();
+    /// assert!(cors.validate().is_ok());
+    /// cors.allow_credentials = Some(true);
+    /// assert!(cors.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.allow_credentials.unwrap_or(false) {
+            return Ok(());
+        }
+        let fields = [
+            ("allow_origins", &self.allow_origins),
+            ("allow_methods", &self.allow_methods),
+            ("allow_headers", &self.allow_headers),
+            ("expose_headers", &self.expose_headers),
+        ];
+        for (name, values) in fields {
+            if values.iter().flatten().any(|value| value == "*") {
+                return Err(format!(
+                    "cors {} \"*\" can not be combined with allow_credentials",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// `RestResp` envelope shape, every field defaults to the classic `{"code", "msg", "data"}`
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct EnvelopeConfig {
+    /// business code field name, default code
+    pub code_field: Option<String>,
+    /// message field name, default msg
+    pub msg_field: Option<String>,
+    /// payload field name, default data
+    pub data_field: Option<String>,
+    /// code of `biz_ok!`, default 200
+    pub success_code: Option<i32>,
+    /// message of `biz_ok`, default Success
+    pub success_msg: Option<String>,
+    /// request id field name (from `x-request-id` or generated), not sent by default
+    pub request_id_field: Option<String>,
+    /// unix timestamp (millisecond) field name, not sent by default
+    pub timestamp_field: Option<String>,
+    /// constant fields added to every envelope, e.g. api_version: v1
+    pub extra: Option<BTreeMap<String, String>>,
+}
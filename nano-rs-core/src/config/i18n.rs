@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// message catalogs of error messages, picked by `Accept-Language`
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct I18nConfig {
+    /// enable i18n, default true
+    pub enable: Option<bool>,
+    /// locale used when `Accept-Language` matches no catalog, default en
+    pub default_locale: Option<String>,
+    /// locale => yaml catalog path, e.g. zh-CN: etc/i18n/zh-CN.yaml
+    pub catalogs: HashMap<String, String>,
+}
+
+impl I18nConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    pub fn get_default_locale(&self) -> String {
+        self.default_locale.clone().unwrap_or("en".to_string())
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// ip allow/deny lists, checked against the secure client ip
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct IpFilterConfig {
+    /// enable ip filter, default true
+    pub enable: Option<bool>,
+    /// only these CIDRs may access the app, e.g. "10.0.0.0/8", empty allows all
+    pub allow: Option<Vec<String>>,
+    /// these CIDRs are always rejected, deny wins over allow
+    pub deny: Option<Vec<String>>,
+    /// extra lists for route path prefixes, checked after the global lists
+    pub routes: Option<Vec<IpFilterRouteConfig>>,
+}
+
+/// ip allow/deny lists of a route path prefix
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct IpFilterRouteConfig {
+    /// request path prefix, "/admin" matches "/admin" and "/admin/users"
+    pub path: String,
+    pub allow: Option<Vec<String>>,
+    pub deny: Option<Vec<String>>,
+}
+
+impl IpFilterConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+}
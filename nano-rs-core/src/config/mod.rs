@@ -10,6 +10,19 @@ pub mod etcd;
 pub mod rpc;
 pub mod prometheus;
 pub mod redis;
+pub mod admin;
+pub mod cors;
+pub mod rate_limit;
+pub mod concurrency;
+pub mod compression;
+pub mod static_files;
+pub mod security_headers;
+pub mod ip_filter;
+pub mod envelope;
+pub mod page;
+pub mod i18n;
+pub mod auth;
+pub mod session;
 
 /// - 从路径加载配置文件
 /// - Load configuration file from config_path
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// `PageQuery` defaults, page size is clamped to `max_size`
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct PageConfig {
+    /// page size when the request has none, default 10
+    pub default_size: Option<u64>,
+    /// max page size, larger sizes are clamped, default 100
+    pub max_size: Option<u64>,
+}
+
+impl PageConfig {
+    pub fn get_default_size(&self) -> u64 {
+        self.default_size.unwrap_or(10).max(1)
+    }
+
+    pub fn get_max_size(&self) -> u64 {
+        self.max_size.unwrap_or(100).max(self.get_default_size())
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// global rate limit config, keyed by client ip
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct RateLimitConfig {
+    /// enable global rate limit, default true
+    pub enable: Option<bool>,
+    /// requests per period, e.g. "100/min", "10/s", "5000/hour"
+    pub rate: String,
+    /// bucket capacity, default the request count of `rate`
+    pub burst: Option<u32>,
+}
+
+impl RateLimitConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+}
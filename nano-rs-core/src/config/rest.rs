@@ -2,9 +2,22 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::admin::AdminConfig;
+use crate::config::auth::AuthConfig;
+use crate::config::compression::CompressionConfig;
+use crate::config::concurrency::ConcurrencyConfig;
+use crate::config::cors::CorsConfig;
+use crate::config::envelope::EnvelopeConfig;
+use crate::config::i18n::I18nConfig;
+use crate::config::ip_filter::IpFilterConfig;
 use crate::config::logger::LogConfig;
+use crate::config::page::PageConfig;
 use crate::config::prometheus::PrometheusConfig;
+use crate::config::rate_limit::RateLimitConfig;
 use crate::config::rpc::RpcConfig;
+use crate::config::security_headers::SecurityHeadersConfig;
+use crate::config::session::SessionConfig;
+use crate::config::static_files::StaticConfig;
 
 /// rest service config
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
@@ -36,6 +49,78 @@ pub struct RestConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// prometheus config
     pub prometheus: Option<PrometheusConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// admin listener config
+    pub admin: Option<AdminConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// cors config
+    pub cors: Option<CorsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// recover handler panics with a 500 response, default true
+    pub panic_recovery: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// global rate limit config
+    pub rate_limit: Option<RateLimitConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// concurrency limit and load shed config
+    pub concurrency: Option<ConcurrencyConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// response compression and request decompression config
+    pub compression: Option<CompressionConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// static file mounts, e.g. a built frontend
+    pub static_files: Option<Vec<StaticConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// security response headers config
+    pub security_headers: Option<SecurityHeadersConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// ip allow/deny lists, global and per route path prefix
+    pub ip_filter: Option<IpFilterConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// http status of `RestResp`, http (default) or always_ok
+    pub status_policy: Option<StatusPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// error body format, envelope (default), problem or problem_unwrapped
+    pub response_format: Option<ResponseFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// `RestResp` envelope field names, success code/msg and extra fields
+    pub envelope: Option<EnvelopeConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// `PageQuery` default and max page size
+    pub page: Option<PageConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// message catalogs of error messages
+    pub i18n: Option<I18nConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// jwt authentication of the routes without `open = true`
+    pub auth: Option<AuthConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// cookie session and csrf protection
+    pub session: Option<SessionConfig>,
+}
+
+/// how `RestResp` maps to http status
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusPolicy {
+    /// error responses carry a real http status (404, 500 etc..)
+    #[default]
+    Http,
+    /// every `RestResp` is sent with 200, the code is only in the body
+    AlwaysOk,
+}
+
+/// body format of `RestResp`
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// `{"code", "msg", "data"}` for every response
+    #[default]
+    Envelope,
+    /// errors as rfc 7807 `application/problem+json`, success keeps the envelope
+    Problem,
+    /// errors as rfc 7807 `application/problem+json`, success is the bare `data`
+    ProblemUnwrapped,
 }
 
 fn default_body_limit() -> usize {
@@ -76,4 +161,21 @@ impl RestConfig {
         }
         Err(String::from("rpc config not found"))
     }
-}
\ No newline at end of file
+
+    /// normalized base path, `None` when routes are served at root ("" or "/")
+    ///
+    /// "v1/" -> "/v1"
+    pub fn get_base_path(&self) -> Option<String> {
+        let base_path = self.base_path.trim().trim_matches('/');
+        if base_path.is_empty() {
+            None
+        } else {
+            Some(format!("/{}", base_path))
+        }
+    }
+
+    /// admin config, only when admin listener is enabled
+    pub fn get_admin_config(&self) -> Option<AdminConfig> {
+        self.admin.clone().filter(|admin| admin.is_enable())
+    }
+}
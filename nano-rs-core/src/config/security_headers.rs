@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// security response headers config, every header has a sane default
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct SecurityHeadersConfig {
+    /// enable security headers, default true
+    pub enable: Option<bool>,
+    /// `Strict-Transport-Security` max-age (second), default one year, 0 disables hsts
+    pub hsts_max_age: Option<u64>,
+    /// hsts includeSubDomains, default true
+    pub hsts_include_subdomains: Option<bool>,
+    /// hsts preload, default false
+    pub hsts_preload: Option<bool>,
+    /// `X-Content-Type-Options: nosniff`, default true
+    pub content_type_options: Option<bool>,
+    /// `X-Frame-Options`, default DENY, "" disables it
+    pub frame_options: Option<String>,
+    /// `Referrer-Policy`, default strict-origin-when-cross-origin, "" disables it
+    pub referrer_policy: Option<String>,
+    /// `Content-Security-Policy`, not set by default
+    pub content_security_policy: Option<String>,
+    /// response headers stripped, default Server and X-Powered-By
+    pub remove_headers: Option<Vec<String>>,
+}
+
+impl SecurityHeadersConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    /// `Strict-Transport-Security` value, `None` when disabled
+    pub fn get_hsts(&self) -> Option<String> {
+        let max_age = self.hsts_max_age.unwrap_or(365 * 24 * 60 * 60);
+        if max_age == 0 {
+            return None;
+        }
+        let mut hsts = format!("max-age={}", max_age);
+        if self.hsts_include_subdomains.unwrap_or(true) {
+            hsts.push_str("; includeSubDomains");
+        }
+        if self.hsts_preload.unwrap_or(false) {
+            hsts.push_str("; preload");
+        }
+        Some(hsts)
+    }
+
+    pub fn get_frame_options(&self) -> Option<String> {
+        non_empty(self.frame_options.clone().unwrap_or("DENY".to_string()))
+    }
+
+    pub fn get_referrer_policy(&self) -> Option<String> {
+        non_empty(
+            self.referrer_policy
+                .clone()
+                .unwrap_or("strict-origin-when-cross-origin".to_string()),
+        )
+    }
+
+    pub fn get_remove_headers(&self) -> Vec<String> {
+        self.remove_headers
+            .clone()
+            .unwrap_or(vec!["server".to_string(), "x-powered-by".to_string()])
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
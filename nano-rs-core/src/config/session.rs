@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::redis::RedisConfig;
+
+/// cookie session of the rest server, non-open generated routes accept a logged in session
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct SessionConfig {
+    /// enable session, default true
+    pub enable: Option<bool>,
+    /// key of the cookie signature and encryption, required
+    pub secret: Option<String>,
+    /// default "nano_session"
+    pub cookie_name: Option<String>,
+    pub domain: Option<String>,
+    /// default "/"
+    pub path: Option<String>,
+    /// default true
+    pub secure: Option<bool>,
+    /// default true
+    pub http_only: Option<bool>,
+    /// Strict, Lax (default) or None
+    pub same_site: Option<String>,
+    /// session lifetime (second), default 86400
+    pub max_age: Option<u64>,
+    /// memory (default) or redis
+    pub store: Option<String>,
+    /// redis of the redis store
+    pub redis: Option<RedisConfig>,
+    /// check csrf token of POST, PUT, PATCH and DELETE requests, default true
+    pub csrf: Option<bool>,
+    /// header carrying the csrf token, default "x-csrf-token"
+    pub csrf_header: Option<String>,
+}
+
+impl SessionConfig {
+    pub fn is_enable(&self) -> bool {
+        self.enable.unwrap_or(true)
+    }
+
+    pub fn get_cookie_name(&self) -> String {
+        self.cookie_name.clone().unwrap_or("nano_session".to_string())
+    }
+
+    pub fn get_path(&self) -> String {
+        self.path.clone().unwrap_or("/".to_string())
+    }
+
+    pub fn is_secure(&self) -> bool {
+        self.secure.unwrap_or(true)
+    }
+
+    pub fn is_http_only(&self) -> bool {
+        self.http_only.unwrap_or(true)
+    }
+
+    pub fn get_same_site(&self) -> String {
+        self.same_site.clone().unwrap_or("Lax".to_string())
+    }
+
+    pub fn get_max_age(&self) -> u64 {
+        self.max_age.unwrap_or(86400)
+    }
+
+    pub fn get_store(&self) -> String {
+        self.store.clone().unwrap_or("memory".to_string())
+    }
+
+    pub fn is_csrf(&self) -> bool {
+        self.csrf.unwrap_or(true)
+    }
+
+    pub fn get_csrf_header(&self) -> String {
+        self.csrf_header.clone().unwrap_or("x-csrf-token".to_string())
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// static file mount, serves `dir` under url `path`
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct StaticConfig {
+    /// url prefix, "/" "/admin"
+    pub path: String,
+    /// directory of the built files
+    pub dir: String,
+    /// `Cache-Control` of served files, e.g. "public, max-age=3600"
+    pub cache_control: Option<String>,
+    /// answer unknown html navigations with the index file, default false
+    pub spa: Option<bool>,
+    /// index file name, default index.html
+    pub index: Option<String>,
+}
+
+impl StaticConfig {
+    pub fn is_spa(&self) -> bool {
+        self.spa.unwrap_or(false)
+    }
+
+    pub fn get_index(&self) -> String {
+        self.index.clone().unwrap_or("index.html".to_string())
+    }
+
+    /// normalized url prefix, "admin/" -> "/admin", "" -> "/"
+    pub fn get_path(&self) -> String {
+        format!("/{}", self.path.trim().trim_matches('/'))
+    }
+}
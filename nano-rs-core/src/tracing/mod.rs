@@ -1,11 +1,16 @@
+use std::sync::OnceLock;
+
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::fmt::writer::MakeWriterExt;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
 
 use crate::config::logger::{LOG_LEVEL, LogFileConfig};
 use crate::config::rest::RestConfig;
 
+static ENV_FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
 /// - 初始化tracing
 /// - Init tracing for project
 /// #Example
@@ -52,6 +57,8 @@ pub fn init_tracing(rest_config: &RestConfig) -> Vec<WorkerGuard> {
     } else {
         env_filter = tracing_subscriber::EnvFilter::new(rest_config.get_env_filter());
     }
+    let (env_filter, handle) = reload::Layer::new(env_filter);
+    let _ = ENV_FILTER_HANDLE.set(handle);
     tracing_subscriber::registry()
         .with(env_filter)
         .with(
@@ -63,4 +70,26 @@ pub fn init_tracing(rest_config: &RestConfig) -> Vec<WorkerGuard> {
         .with(layers)
         .init();
     guards
-}
\ No newline at end of file
+}
+
+/// - 获取当前日志过滤规则
+/// - Get current tracing env filter directives, `None` before `init_tracing`
+pub fn get_env_filter() -> Option<String> {
+    ENV_FILTER_HANDLE
+        .get()
+        .and_then(|handle| handle.with_current(|filter| filter.to_string()).ok())
+}
+
+/// - 运行时修改日志过滤规则
+/// - Reload tracing env filter at runtime
+/// #Example
+/// ```rust
+/// let _ = nano_rs_core::tracing::reload_env_filter("info,tower_http=debug");
+/// ```
+pub fn reload_env_filter(directives: &str) -> Result<(), String> {
+    let handle = ENV_FILTER_HANDLE
+        .get()
+        .ok_or("tracing not initialized".to_string())?;
+    let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+    handle.reload(filter).map_err(|e| e.to_string())
+}
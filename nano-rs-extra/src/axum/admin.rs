@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::Router;
+use nano_rs_core::config::rest::RestConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::axum::errors::ServerError;
+use crate::axum::extractor::Json;
+use crate::axum::generator::gen_api_info::ApiInfo;
+use crate::axum::middleware::prometheus::metrics_handler;
+use crate::axum::rest::{biz_err, biz_ok, RestResp};
+
+/// prometheus metrics route path
+pub const METRICS_PATH: &str = "/metrics";
+
+/// log level route path
+pub const LOG_LEVEL_PATH: &str = "/log/level";
+
+/// config dump route path
+pub const CONFIG_PATH: &str = "/config";
+
+/// api route listing path
+pub const ROUTES_PATH: &str = "/routes";
+
+/// config keys masked in the config dump
+const SECRET_KEYS: [&str; 3] = ["secret", "password", "pass_word"];
+
+/// placeholder of masked secrets
+const REDACTED: &str = "******";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogLevel {
+    /// tracing env filter directives, e.g. "info,tower_http=debug"
+    pub filter: String,
+}
+
+/// operational routes served by the admin listener
+pub fn admin_routes(rest_config: Arc<RestConfig>) -> Router {
+    Router::new()
+        .route(METRICS_PATH, get(metrics_handler))
+        .route(LOG_LEVEL_PATH, get(get_log_level).put(set_log_level))
+        .route(CONFIG_PATH, get(get_config))
+        .with_state(rest_config)
+}
+
+/// `ApiInfo` route listing, feed it with the generated `get_api_info()`
+pub fn api_info_routes(api_info: Vec<ApiInfo>) -> Router {
+    Router::new()
+        .route(ROUTES_PATH, get(get_routes))
+        .with_state(Arc::new(api_info))
+}
+
+pub async fn get_log_level() -> Result<RestResp<LogLevel>, ServerError> {
+    match nano_rs_core::tracing::get_env_filter() {
+        None => biz_err(500, "tracing not initialized"),
+        Some(filter) => biz_ok(200, LogLevel { filter }),
+    }
+}
+
+pub async fn set_log_level(Json(level): Json<LogLevel>) -> Result<RestResp<LogLevel>, ServerError> {
+    match nano_rs_core::tracing::reload_env_filter(level.filter.as_str()) {
+        Ok(()) => {
+            tracing::info!("log level changed to {}", level.filter);
+            biz_ok(200, level)
+        }
+        Err(err) => biz_err(400, err),
+    }
+}
+
+/// rest config, secrets (jwt and session keys, passwords) are redacted
+pub async fn get_config(
+    State(rest_config): State<Arc<RestConfig>>,
+) -> Result<RestResp<Value>, ServerError> {
+    match serde_json::to_value(rest_config.as_ref()) {
+        Ok(mut config) => {
+            redact(&mut config);
+            biz_ok(200, config)
+        }
+        Err(err) => biz_err(500, err.to_string()),
+    }
+}
+
+/// mask the non empty values of `SECRET_KEYS` in place
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(secret) if SECRET_KEYS.contains(&key.as_str()) => {
+                        if !secret.is_empty() {
+                            *secret = REDACTED.to_string();
+                        }
+                    }
+                    _ => redact(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+pub async fn get_routes(
+    State(api_info): State<Arc<Vec<ApiInfo>>>,
+) -> Result<RestResp<Vec<ApiInfo>>, ServerError> {
+    biz_ok(200, api_info.as_ref().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nano_rs_core::config::auth::AuthConfig;
+    use nano_rs_core::config::db::DataBaseConfig;
+
+    #[test]
+    fn redact_secrets() {
+        let config = RestConfig {
+            auth: Some(AuthConfig {
+                secret: Some("jwt-secret".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut value = serde_json::to_value(&config).unwrap();
+        redact(&mut value);
+        assert_eq!(value["auth"]["secret"], REDACTED);
+        // the config itself still round trips with its secrets
+        let config: RestConfig = serde_json::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+        assert_eq!(config.auth.unwrap().secret.as_deref(), Some("jwt-secret"));
+
+        let mut value = serde_json::json!({
+            "database": [serde_json::to_value(DataBaseConfig {
+                password: "db-pass".to_string(),
+                ..Default::default()
+            }).unwrap()],
+            "etcd": {"pass_word": ""},
+        });
+        redact(&mut value);
+        assert_eq!(value["database"][0]["password"], REDACTED);
+        assert_eq!(value["etcd"]["pass_word"], "");
+    }
+}
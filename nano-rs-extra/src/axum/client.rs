@@ -0,0 +1,534 @@
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri};
+use axum::Router;
+use bytes::Bytes;
+use http_body_util::BodyExt;
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tower::ServiceExt;
+
+use crate::axum::problem::{self, Problem, APPLICATION_PROBLEM_JSON};
+use crate::axum::rest::{envelope, Envelope, ResponseFormat, RestResp};
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// path, query or body can not be encoded
+    Encode(String),
+    Transport(String),
+    /// response body is not a `RestResp` of the client envelope and format
+    Decode { status: StatusCode, body: String },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Encode(msg) => write!(f, "encode request failed: {}", msg),
+            ClientError::Transport(msg) => write!(f, "send request failed: {}", msg),
+            ClientError::Decode { status, body } => write!(f, "invalid response ({}): {}", status, body),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// boxed future returned by [`Transport::send`]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response<Bytes>, ClientError>> + Send + 'a>>;
+
+/// 请求发送
+/// Send a request with an absolute uri, implement it for other http clients (tls, pooling etc..)
+pub trait Transport: Send + Sync {
+    fn send(&self, req: Request<Body>) -> TransportFuture<'_>;
+}
+
+async fn collect<B>(res: Response<B>) -> Result<Response<Bytes>, ClientError>
+where
+    B: axum::body::HttpBody,
+    B::Error: Display,
+{
+    let (parts, body) = res.into_parts();
+    let body = body
+        .collect()
+        .await
+        .map_err(|e| ClientError::Transport(e.to_string()))?
+        .to_bytes();
+    Ok(Response::from_parts(parts, body))
+}
+
+/// plain http/1.1, one connection per request
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransport;
+
+impl Transport for HttpTransport {
+    fn send(&self, mut req: Request<Body>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            if req.uri().scheme_str() == Some("https") {
+                return Err(ClientError::Transport("https is not supported by HttpTransport".to_string()));
+            }
+            let authority = req
+                .uri()
+                .authority()
+                .cloned()
+                .ok_or(ClientError::Transport(format!("missing host of {}", req.uri())))?;
+            let stream = TcpStream::connect((authority.host(), authority.port_u16().unwrap_or(80)))
+                .await
+                .map_err(|e| ClientError::Transport(format!("connect {} failed: {}", authority, e)))?;
+            let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+                .await
+                .map_err(|e| ClientError::Transport(e.to_string()))?;
+            tokio::spawn(async move {
+                if let Err(err) = connection.await {
+                    tracing::debug!("client connection closed: {}", err);
+                }
+            });
+            let path_and_query = req
+                .uri()
+                .path_and_query()
+                .map(|path_and_query| path_and_query.as_str())
+                .unwrap_or("/")
+                .parse::<Uri>()
+                .map_err(|e| ClientError::Encode(e.to_string()))?;
+            *req.uri_mut() = path_and_query;
+            req.headers_mut().insert(
+                header::HOST,
+                HeaderValue::from_str(authority.as_str()).map_err(|e| ClientError::Encode(e.to_string()))?,
+            );
+            let res = sender
+                .send_request(req)
+                .await
+                .map_err(|e| ClientError::Transport(e.to_string()))?;
+            collect(res).await
+        })
+    }
+}
+
+/// call the router in process, no listener needed (integration tests etc..)
+impl Transport for Router {
+    fn send(&self, req: Request<Body>) -> TransportFuture<'_> {
+        let router = self.clone();
+        Box::pin(async move {
+            let res = router
+                .oneshot(req)
+                .await
+                .map_err(|e| ClientError::Transport(e.to_string()))?;
+            collect(res).await
+        })
+    }
+}
+
+/// 客户端请求
+/// Request of a generated client method, `{name}` of the path is filled by `path`
+#[derive(Debug, Clone)]
+pub struct ClientRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: Option<String>,
+    pub headers: HeaderMap,
+    pub body: Option<(&'static str, Vec<u8>)>,
+}
+
+impl ClientRequest {
+    pub fn new(method: Method, path: &str) -> Self {
+        ClientRequest {
+            method,
+            path: path.to_string(),
+            query: None,
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// fill path params by a struct (field names), a tuple (in order) or a single value
+    pub fn path<P: Serialize + ?Sized>(mut self, params: &P) -> Result<Self, ClientError> {
+        let params = serde_json::to_value(params).map_err(|e| ClientError::Encode(e.to_string()))?;
+        let names = placeholders(self.path.as_str());
+        let values: Vec<(String, Value)> = match params {
+            Value::Object(mut fields) => names
+                .into_iter()
+                .filter_map(|name| {
+                    let value = fields.remove(name.trim_start_matches('*'))?;
+                    Some((name, value))
+                })
+                .collect(),
+            Value::Array(values) => names.into_iter().zip(values).collect(),
+            value => names.into_iter().take(1).map(|name| (name, value.clone())).collect(),
+        };
+        for (name, value) in values {
+            let value = match value {
+                Value::String(value) => value,
+                Value::Number(value) => value.to_string(),
+                Value::Bool(value) => value.to_string(),
+                value => return Err(ClientError::Encode(format!("invalid path param {}: {}", name, value))),
+            };
+            let value = encode_path(value.as_str(), name.starts_with('*'));
+            self.path = self.path.replace(format!("{{{}}}", name).as_str(), value.as_str());
+        }
+        Ok(self)
+    }
+
+    pub fn query<Q: Serialize + ?Sized>(mut self, query: &Q) -> Result<Self, ClientError> {
+        let query = serde_urlencoded::to_string(query).map_err(|e| ClientError::Encode(e.to_string()))?;
+        self.query = match self.query.take() {
+            Some(before) if !query.is_empty() => Some(format!("{}&{}", before, query)),
+            before => before.or(Some(query)),
+        };
+        Ok(self)
+    }
+
+    pub fn json<B: Serialize + ?Sized>(mut self, body: &B) -> Result<Self, ClientError> {
+        let body = serde_json::to_vec(body).map_err(|e| ClientError::Encode(e.to_string()))?;
+        self.body = Some(("application/json", body));
+        Ok(self)
+    }
+
+    pub fn form<B: Serialize + ?Sized>(mut self, body: &B) -> Result<Self, ClientError> {
+        let body = serde_urlencoded::to_string(body).map_err(|e| ClientError::Encode(e.to_string()))?;
+        self.body = Some(("application/x-www-form-urlencoded", body.into_bytes()));
+        Ok(self)
+    }
+
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+}
+
+/// names of the `{name}` placeholders of a route path, in order
+fn placeholders(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+/// percent encode all but unreserved chars (and `/` of wildcard params)
+fn encode_path(value: &str, keep_slash: bool) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            b'/' if keep_slash => encoded.push('/'),
+            _ => encoded.push_str(format!("%{:02X}", byte).as_str()),
+        }
+    }
+    encoded
+}
+
+/// 接口客户端
+/// Client of a nano-rs server, used by the generated `ApiClient`
+///
+/// responses are decoded as `RestResp` with the envelope and response format of the app (see
+/// `set_envelope` and `set_response_format`) whatever the http status, `application/problem+json`
+/// errors get the `code` extension (else the status) and the `detail` (else the title) as msg
+///
+/// # Example
+/// ```rust
+/// use axum::http::Method;
+/// use axum::routing::get;
+/// use axum::Router;
+/// use nano_rs_extra::axum::client::{ClientRequest, RestClient};
+/// use nano_rs_extra::axum::errors::ServerError;
+/// use nano_rs_extra::axum::extractor::Path;
+/// use nano_rs_extra::axum::rest::{biz_ok, RestResp};
+///
+/// async fn hello(Path(name): Path<String>) -> Result<RestResp<String>, ServerError> {
+///     biz_ok(200, format!("hello {}", name))
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let client = RestClient::with_router(Router::new().route("/hello/{name}", get(hello)));
+/// let req = ClientRequest::new(Method::GET, "/hello/{name}").path("mantou").unwrap();
+/// let resp = client.send::<String>(req).await.unwrap();
+/// assert_eq!(resp.data.as_deref(), Some("hello mantou"));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct RestClient {
+    /// e.g. "http://pet-service:8888", with the `base_path` of the server if any
+    pub base_url: String,
+    /// sent with every request, e.g. `Authorization`
+    pub headers: HeaderMap,
+    /// field names and success code/msg of the server envelope
+    pub envelope: Envelope,
+    /// success bodies are the bare `data` under `ProblemUnwrapped`
+    pub response_format: ResponseFormat,
+    transport: Arc<dyn Transport>,
+}
+
+impl RestClient {
+    /// plain http client, see `HttpTransport`
+    pub fn new(base_url: &str) -> Self {
+        RestClient::with_transport(base_url, HttpTransport)
+    }
+
+    /// call the router in process
+    pub fn with_router(router: Router) -> Self {
+        RestClient::with_transport("http://localhost", router)
+    }
+
+    pub fn with_transport<T: Transport + 'static>(base_url: &str, transport: T) -> Self {
+        RestClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            headers: HeaderMap::new(),
+            envelope: envelope().as_ref().clone(),
+            response_format: problem::response_format(),
+            transport: Arc::new(transport),
+        }
+    }
+
+    /// envelope of a server configured unlike this app
+    pub fn envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    pub fn response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.response_format = response_format;
+        self
+    }
+
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// `Authorization: Bearer <token>`, tokens with invalid header chars are skipped
+    pub fn bearer(self, token: &str) -> Self {
+        match HeaderValue::from_str(format!("Bearer {}", token).as_str()) {
+            Ok(value) => self.header(header::AUTHORIZATION, value),
+            Err(_) => {
+                tracing::warn!("invalid bearer token skipped");
+                self
+            }
+        }
+    }
+
+    pub async fn send<T: DeserializeOwned>(&self, req: ClientRequest) -> Result<RestResp<T>, ClientError> {
+        let uri = match req.query.as_ref().filter(|query| !query.is_empty()) {
+            Some(query) => format!("{}{}?{}", self.base_url, req.path, query),
+            None => format!("{}{}", self.base_url, req.path),
+        };
+        let mut builder = Request::builder().method(req.method).uri(uri);
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers.clone());
+            headers.extend(req.headers);
+        }
+        let body = match req.body {
+            Some((content_type, body)) => {
+                builder = builder.header(header::CONTENT_TYPE, content_type);
+                Body::from(body)
+            }
+            None => Body::empty(),
+        };
+        let request = builder.body(body).map_err(|e| ClientError::Encode(e.to_string()))?;
+        let res = self.transport.send(request).await?;
+        let status = res.status();
+        let mut resp: RestResp<T> = self.decode(&res).ok_or_else(|| ClientError::Decode {
+            status,
+            body: String::from_utf8_lossy(res.body()).to_string(),
+        })?;
+        resp.status = Some(status);
+        Ok(resp)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, res: &Response<Bytes>) -> Option<RestResp<T>> {
+        let content_type = res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if content_type.starts_with(APPLICATION_PROBLEM_JSON) {
+            let problem: Problem = serde_json::from_slice(res.body()).ok()?;
+            let code = problem
+                .extensions
+                .get("code")
+                .and_then(|code| code.as_i64())
+                .and_then(|code| i32::try_from(code).ok())
+                .unwrap_or(problem.status as i32);
+            return Some(RestResp {
+                code,
+                msg: problem.detail.unwrap_or(problem.title),
+                data: None,
+                status: None,
+            });
+        }
+        let body: Value = serde_json::from_slice(res.body()).ok()?;
+        if self.response_format == ResponseFormat::ProblemUnwrapped {
+            return Some(RestResp {
+                code: self.envelope.success_code,
+                msg: self.envelope.success_msg.clone(),
+                data: serde_json::from_value(body).ok()?,
+                status: None,
+            });
+        }
+        let Value::Object(mut body) = body else {
+            return None;
+        };
+        let code = body.get(self.envelope.code_field.as_str())?.as_i64()?;
+        let msg = match body.remove(self.envelope.msg_field.as_str()) {
+            Some(Value::String(msg)) => msg,
+            _ => String::new(),
+        };
+        let data = body.remove(self.envelope.data_field.as_str()).unwrap_or(Value::Null);
+        Some(RestResp {
+            code: i32::try_from(code).ok()?,
+            msg,
+            data: serde_json::from_value(data).ok()?,
+            status: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::routing::{get, post};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+    use crate::axum::errors::ServerError;
+    use crate::axum::extractor::{Json, Path, Query};
+    use crate::axum::rest::{biz_err, biz_ok};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Pet {
+        id: i64,
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct QueryPet {
+        name: String,
+    }
+
+    async fn get_pet(Path(id): Path<i64>, Query(query): Query<QueryPet>) -> Result<RestResp<Pet>, ServerError> {
+        if id == 0 {
+            return biz_err(404, "pet not found");
+        }
+        biz_ok(200, Pet { id, name: query.name })
+    }
+
+    async fn add_pet(Json(pet): Json<Pet>) -> Result<RestResp<Pet>, ServerError> {
+        biz_ok(200, pet)
+    }
+
+    /// what `AxumGenClient` generates for the routes above
+    struct ApiClient {
+        client: RestClient,
+    }
+
+    impl ApiClient {
+        async fn get_pet(&self, id: &i64, query: &QueryPet) -> Result<RestResp<Pet>, ClientError> {
+            let req = ClientRequest::new(Method::GET, "/store/pet/{id}").path(id)?.query(query)?;
+            self.client.send(req).await
+        }
+
+        async fn add_pet(&self, pet: &Pet) -> Result<RestResp<Pet>, ClientError> {
+            let req = ClientRequest::new(Method::POST, "/store/pet").json(pet)?;
+            self.client.send(req).await
+        }
+    }
+
+    fn api_client() -> ApiClient {
+        let router = Router::new()
+            .route("/store/pet/{id}", get(get_pet))
+            .route("/store/pet", post(add_pet));
+        ApiClient {
+            client: RestClient::with_router(router),
+        }
+    }
+
+    #[tokio::test]
+    async fn call_generated_methods() {
+        let client = api_client();
+        let query = QueryPet {
+            name: "man tou".to_string(),
+        };
+        let resp = client.get_pet(&7, &query).await.unwrap();
+        assert_eq!(resp.status, Some(StatusCode::OK));
+        assert_eq!(resp.code, 200);
+        assert_eq!(
+            resp.data,
+            Some(Pet {
+                id: 7,
+                name: "man tou".to_string()
+            })
+        );
+        let resp = client.get_pet(&0, &query).await.unwrap();
+        assert_eq!(resp.status, Some(StatusCode::NOT_FOUND));
+        assert_eq!((resp.code, resp.msg.as_str(), resp.data), (404, "pet not found", None));
+        let pet = Pet {
+            id: 1,
+            name: "mantou".to_string(),
+        };
+        assert_eq!(client.add_pet(&pet).await.unwrap().data, Some(pet));
+    }
+
+    #[test]
+    fn fill_path_params() {
+        let req = ClientRequest::new(Method::GET, "/a/{id}/{name}")
+            .path(&json!({"id": 1, "name": "a/b c"}))
+            .unwrap();
+        assert_eq!(req.path, "/a/1/a%2Fb%20c");
+        let req = ClientRequest::new(Method::GET, "/a/{page}/{size}").path(&(2, 10)).unwrap();
+        assert_eq!(req.path, "/a/2/10");
+        let req = ClientRequest::new(Method::GET, "/files/{*path}").path("a/b c").unwrap();
+        assert_eq!(req.path, "/files/a/b%20c");
+    }
+
+    fn client_of(body: Value, content_type: &'static str, status: StatusCode) -> RestClient {
+        let router = Router::new().route(
+            "/pet",
+            get(move || async move { (status, [(header::CONTENT_TYPE, content_type)], body.to_string()) }),
+        );
+        RestClient::with_router(router)
+    }
+
+    #[tokio::test]
+    async fn decode_renamed_envelope() {
+        let body = json!({"errno": 0, "message": "ok", "result": {"id": 1, "name": "mantou"}, "ts": 1});
+        let client = client_of(body, "application/json", StatusCode::OK).envelope(Envelope {
+            code_field: "errno".to_string(),
+            msg_field: "message".to_string(),
+            data_field: "result".to_string(),
+            success_code: 0,
+            ..Envelope::default()
+        });
+        let resp = client.send::<Pet>(ClientRequest::new(Method::GET, "/pet")).await.unwrap();
+        assert_eq!((resp.code, resp.msg.as_str()), (0, "ok"));
+        assert_eq!(resp.data.unwrap().name, "mantou");
+        // the default envelope does not match
+        let client = client.envelope(Envelope::default());
+        let err = client.send::<Pet>(ClientRequest::new(Method::GET, "/pet")).await;
+        assert!(matches!(err, Err(ClientError::Decode { .. })));
+    }
+
+    #[tokio::test]
+    async fn decode_problem() {
+        let body = json!({"type": "about:blank", "title": "Not Found", "status": 404, "detail": "pet not found", "code": 40001});
+        let client = client_of(body, APPLICATION_PROBLEM_JSON, StatusCode::NOT_FOUND);
+        let resp = client.send::<Pet>(ClientRequest::new(Method::GET, "/pet")).await.unwrap();
+        assert_eq!(resp.status, Some(StatusCode::NOT_FOUND));
+        assert_eq!((resp.code, resp.msg.as_str(), resp.data), (40001, "pet not found", None));
+        let body = json!({"type": "about:blank", "title": "Bad Request", "status": 400});
+        let client = client_of(body, APPLICATION_PROBLEM_JSON, StatusCode::BAD_REQUEST);
+        let resp = client.send::<Pet>(ClientRequest::new(Method::GET, "/pet")).await.unwrap();
+        assert_eq!((resp.code, resp.msg.as_str()), (400, "Bad Request"));
+    }
+
+    #[tokio::test]
+    async fn decode_unwrapped_data() {
+        let body = json!({"id": 1, "name": "mantou"});
+        let client = client_of(body, "application/json", StatusCode::OK).response_format(ResponseFormat::ProblemUnwrapped);
+        let resp = client.send::<Pet>(ClientRequest::new(Method::GET, "/pet")).await.unwrap();
+        assert_eq!((resp.code, resp.msg.as_str()), (200, "Success"));
+        assert_eq!(resp.data.unwrap().id, 1);
+    }
+}
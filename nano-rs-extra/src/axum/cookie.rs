@@ -0,0 +1,300 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use axum::http::{header, HeaderMap, HeaderValue};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+impl FromStr for SameSite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(SameSite::Strict),
+            "lax" => Ok(SameSite::Lax),
+            "none" => Ok(SameSite::None),
+            _ => Err(format!("invalid same site: {}", s)),
+        }
+    }
+}
+
+/// `Set-Cookie` 构建
+/// Cookie sent by `Set-Cookie`, value is written as is (sign or encrypt it with `CookieKey`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub domain: Option<String>,
+    pub path: Option<String>,
+    /// second
+    pub max_age: Option<u64>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    pub fn new(name: &str, value: &str) -> Self {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: None,
+            path: None,
+            max_age: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    /// expire the cookie `name` in the browser, path and domain must match the original one
+    pub fn removal(name: &str) -> Self {
+        Cookie::new(name, "").max_age(0)
+    }
+
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(domain.to_string());
+        self
+    }
+
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    pub fn max_age(mut self, max_age: u64) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    pub fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+}
+
+impl Display for Cookie {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age)?;
+        }
+        if self.secure {
+            write!(f, "; Secure")?;
+        }
+        if self.http_only {
+            write!(f, "; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// value of cookie `name` in the `Cookie` headers of a request
+pub fn get_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            (key == name).then(|| value.trim_matches('"').to_string())
+        })
+}
+
+/// append a `Set-Cookie` header, cookies with invalid header chars are skipped
+pub fn set_cookie(headers: &mut HeaderMap, cookie: &Cookie) {
+    match HeaderValue::from_str(cookie.to_string().as_str()) {
+        Ok(value) => {
+            headers.append(header::SET_COOKIE, value);
+        }
+        Err(_) => tracing::warn!("invalid cookie {} skipped", cookie.name),
+    }
+}
+
+/// nonce size of AES-256-GCM, prefixed to encrypted values
+const NONCE_LEN: usize = 12;
+
+/// 签名/加密 cookie
+/// Sign (HMAC-SHA256) or encrypt (AES-256-GCM) cookie values, keys are derived from one secret
+///
+/// the cookie name is bound to the value, so a value can not be moved to another cookie
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::cookie::CookieKey;
+///
+/// let key = CookieKey::from_secret(b"secret");
+/// let signed = key.sign("user", "mantou");
+/// assert_eq!(key.verify("user", signed.as_str()).as_deref(), Some("mantou"));
+/// assert_eq!(key.verify("admin", signed.as_str()), None);
+///
+/// let encrypted = key.encrypt("user", "mantou");
+/// assert_eq!(key.decrypt("user", encrypted.as_str()).as_deref(), Some("mantou"));
+/// ```
+#[derive(Clone)]
+pub struct CookieKey {
+    signing: Vec<u8>,
+    encryption: Vec<u8>,
+}
+
+impl CookieKey {
+    pub fn from_secret(secret: &[u8]) -> Self {
+        let derive = |purpose: &[u8]| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("hmac accepts any key length");
+            mac.update(purpose);
+            mac.finalize().into_bytes().to_vec()
+        };
+        CookieKey {
+            signing: derive(b"nano-rs cookie signing"),
+            encryption: derive(b"nano-rs cookie encryption"),
+        }
+    }
+
+    fn mac(&self, name: &str, value: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.signing.as_slice()).expect("hmac accepts any key length");
+        mac.update(name.as_bytes());
+        mac.update(b"=");
+        mac.update(value.as_bytes());
+        mac
+    }
+
+    /// `value.signature`
+    pub fn sign(&self, name: &str, value: &str) -> String {
+        let signature = self.mac(name, value).finalize().into_bytes();
+        format!("{}.{}", value, URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    /// value of a signed cookie, none when the signature does not match
+    pub fn verify(&self, name: &str, signed: &str) -> Option<String> {
+        let (value, signature) = signed.rsplit_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        self.mac(name, value)
+            .verify_slice(signature.as_slice())
+            .ok()
+            .map(|_| value.to_string())
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new_from_slice(self.encryption.as_slice()).expect("32 bytes aes key")
+    }
+
+    /// base64 of nonce, cipher text and tag
+    pub fn encrypt(&self, name: &str, value: &str) -> String {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let cipher = self
+            .cipher()
+            .encrypt(&nonce, Payload { msg: value.as_bytes(), aad: name.as_bytes() })
+            .expect("aes-gcm encrypt");
+        let mut data = nonce.to_vec();
+        data.extend(cipher);
+        URL_SAFE_NO_PAD.encode(data)
+    }
+
+    /// value of an encrypted cookie, none when it is tampered or of another key
+    pub fn decrypt(&self, name: &str, encrypted: &str) -> Option<String> {
+        let data = URL_SAFE_NO_PAD.decode(encrypted).ok()?;
+        if data.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, cipher) = data.split_at(NONCE_LEN);
+        let value = self
+            .cipher()
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: cipher, aad: name.as_bytes() })
+            .ok()?;
+        String::from_utf8(value).ok()
+    }
+}
+
+/// random url safe token of 32 bytes, session ids and csrf tokens
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_cookie_rejects_tampering() {
+        let key = CookieKey::from_secret(b"secret");
+        let signed = key.sign("user", "mantou");
+        assert_eq!(key.verify("user", signed.as_str()).as_deref(), Some("mantou"));
+        let (_, signature) = signed.rsplit_once('.').unwrap();
+        assert_eq!(key.verify("user", format!("admin.{}", signature).as_str()), None);
+        assert_eq!(key.verify("user", format!("{}x", signed).as_str()), None);
+        assert_eq!(key.verify("user", "mantou"), None);
+        assert_eq!(key.verify("admin", signed.as_str()), None);
+        assert_eq!(CookieKey::from_secret(b"other").verify("user", signed.as_str()), None);
+    }
+
+    #[test]
+    fn encrypted_cookie_rejects_tampering() {
+        let key = CookieKey::from_secret(b"secret");
+        let encrypted = key.encrypt("user", "mantou");
+        assert_ne!(encrypted, key.encrypt("user", "mantou"));
+        assert!(!encrypted.contains("mantou"));
+        assert_eq!(key.decrypt("user", encrypted.as_str()).as_deref(), Some("mantou"));
+        let mut data = URL_SAFE_NO_PAD.decode(encrypted.as_str()).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        assert_eq!(key.decrypt("user", URL_SAFE_NO_PAD.encode(data).as_str()), None);
+        assert_eq!(key.decrypt("admin", encrypted.as_str()), None);
+        assert_eq!(CookieKey::from_secret(b"other").decrypt("user", encrypted.as_str()), None);
+        assert_eq!(key.decrypt("user", "short"), None);
+        assert_eq!(key.decrypt("user", "not base64!"), None);
+    }
+
+    #[test]
+    fn read_and_write_cookies() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, HeaderValue::from_static("a=1; nano_session=\"abc\""));
+        assert_eq!(get_cookie(&headers, "nano_session").as_deref(), Some("abc"));
+        assert_eq!(get_cookie(&headers, "b"), None);
+        set_cookie(&mut headers, &Cookie::new("a", "2").path("/").http_only(true).same_site(SameSite::Lax));
+        assert_eq!(headers[header::SET_COOKIE], "a=2; Path=/; HttpOnly; SameSite=Lax");
+    }
+}
@@ -1,16 +1,24 @@
+use crate::axum::i18n;
 use crate::axum::rest::RestResp;
+use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
 pub struct ServerError(pub anyhow::Error);
 
 impl IntoResponse for ServerError {
     fn into_response(self) -> Response {
+        if let Some(biz_error) = self.0.downcast_ref::<BizErrorInfo>() {
+            return biz_error.clone().into_response();
+        }
         tracing::error!("INTERNAL_SERVER_ERROR: {} - {:#?}", self.0, self.0.backtrace());
         RestResp::<()> {
             code: 500,
-            msg: "INTERNAL_SERVER_ERROR".to_string(),
+            msg: i18n::lookup("error.internal_server_error", &[])
+                .unwrap_or("INTERNAL_SERVER_ERROR".to_string()),
             data: None,
+            status: Some(StatusCode::INTERNAL_SERVER_ERROR),
         }
             .into_response()
     }
@@ -24,3 +32,96 @@ impl<E> From<E> for ServerError
         Self(err.into())
     }
 }
+
+/// 业务错误码
+/// Typed business error, implemented by `#[derive(BizError)]` of nano-rs-macro
+///
+/// # Example
+/// ```rust,ignore
+/// use nano_rs::BizError;
+///
+/// #[derive(Debug, BizError)]
+/// pub enum PetError {
+///     #[biz_error(code = 40001, msg = "pet not found", status = 404, key = "pet.not_found")]
+///     NotFound,
+///     #[biz_error(code = 40002, msg = "pet name is taken")]
+///     NameTaken,
+/// }
+///
+/// pub async fn get_pet() -> Result<RestResp<Pet>, ServerError> {
+///     Err(PetError::NotFound)?
+/// }
+/// ```
+pub trait BizError {
+    /// `RestResp.code`
+    fn code(&self) -> i32;
+
+    /// `RestResp.msg`
+    fn msg(&self) -> &'static str;
+
+    /// http status, default the code when it is in 400..600 else 200, like `biz_err`
+    fn status(&self) -> u16;
+
+    /// every error code of the enum, e.g. for the api doc
+    fn all() -> Vec<BizErrorInfo>
+    where
+        Self: Sized;
+
+    /// message key in the i18n catalogs, `msg` is the fallback
+    fn key(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// args of the i18n message, the variant fields ("0", "1".. or field names)
+    fn args(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// code, msg (in the request locale when `key` is set) and http status
+    fn info(&self) -> BizErrorInfo {
+        let msg = self
+            .key()
+            .and_then(|key| i18n::lookup(key, &self.args()))
+            .unwrap_or(self.msg().to_string());
+        BizErrorInfo {
+            code: self.code(),
+            msg,
+            status: self.status(),
+        }
+    }
+
+    /// `ServerError` carrying `info`, for enums derived with `#[biz_error(skip_from)]`
+    fn server_error(&self) -> ServerError {
+        ServerError(self.info().into())
+    }
+}
+
+/// code, msg and http status of a business error
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct BizErrorInfo {
+    pub code: i32,
+    pub msg: String,
+    pub status: u16,
+}
+
+impl std::fmt::Display for BizErrorInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.msg)
+    }
+}
+
+/// carried by `ServerError` when a `BizError` is raised with `?`
+impl std::error::Error for BizErrorInfo {}
+
+impl IntoResponse for BizErrorInfo {
+    fn into_response(self) -> Response {
+        tracing::error!("code: {} - {:#?}", self.code, self.msg);
+        RestResp::<()> {
+            code: self.code,
+            msg: self.msg,
+            data: None,
+            status: StatusCode::from_u16(self.status).ok(),
+        }
+        .into_response()
+    }
+}
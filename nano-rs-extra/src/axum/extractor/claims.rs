@@ -0,0 +1,52 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use crate::axum::extractor::Claims;
+use crate::axum::middleware::auth::VerifiedClaims;
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use serde::de::DeserializeOwned;
+
+fn decode<T: DeserializeOwned>(claims: &VerifiedClaims) -> Result<T, RestRejection> {
+    serde_json::from_value(claims.0.clone()).map_err(|err| {
+        rest_rejection(
+            "claims",
+            StatusCode::UNAUTHORIZED,
+            format!("rejection: invalid claims: {}", err),
+        )
+    })
+}
+
+impl<S, T> FromRequestParts<S> for Claims<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.extensions.get::<VerifiedClaims>() {
+            Some(claims) => decode(claims).map(Self),
+            None => Err(rest_rejection(
+                "claims",
+                StatusCode::UNAUTHORIZED,
+                "rejection: missing claims, the route is open or auth is not configured"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+impl<S, T> OptionalFromRequestParts<S> for Claims<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Option<Self>, Self::Rejection> {
+        match parts.extensions.get::<VerifiedClaims>() {
+            Some(claims) => decode(claims).map(|claims| Some(Self(claims))),
+            None => Ok(None),
+        }
+    }
+}
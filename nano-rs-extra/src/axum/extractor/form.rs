@@ -0,0 +1,23 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use crate::axum::extractor::Form;
+use axum::extract::rejection::FormRejection;
+use axum::extract::{FromRequest, Request};
+
+impl<S, T> FromRequest<S> for Form<T>
+where
+    axum::Form<T>: FromRequest<S, Rejection = FormRejection>,
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::Form::<T>::from_request(req, state).await {
+            Ok(value) => Ok(Self(value.0)),
+            Err(rejection) => Err(rest_rejection(
+                "form",
+                rejection.status(),
+                format!("rejection: {}", rejection.body_text()),
+            )),
+        }
+    }
+}
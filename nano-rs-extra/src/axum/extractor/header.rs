@@ -0,0 +1,81 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use crate::axum::extractor::Header;
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+
+/// 类型化请求头
+/// A request header decoded into a type, read with the `Header` extractor
+///
+/// # Example
+/// ```rust
+/// use axum::http::{HeaderName, HeaderValue};
+/// use nano_rs_extra::axum::extractor::Header;
+/// use nano_rs_extra::axum::extractor::header::TypedHeader;
+///
+/// pub struct TenantId(pub i64);
+///
+/// impl TypedHeader for TenantId {
+///     fn name() -> HeaderName {
+///         HeaderName::from_static("x-tenant-id")
+///     }
+///
+///     fn decode(value: &HeaderValue) -> Result<Self, String> {
+///         let value = value.to_str().map_err(|err| err.to_string())?;
+///         value.parse().map(TenantId).map_err(|_| format!("not a tenant id: {}", value))
+///     }
+/// }
+///
+/// pub async fn tenant(Header(tenant): Header<TenantId>) -> String {
+///     tenant.0.to_string()
+/// }
+/// ```
+pub trait TypedHeader: Sized {
+    fn name() -> HeaderName;
+
+    fn decode(value: &HeaderValue) -> Result<Self, String>;
+}
+
+fn decode<T: TypedHeader>(value: &HeaderValue) -> Result<T, RestRejection> {
+    T::decode(value).map_err(|err| {
+        rest_rejection(
+            "header",
+            StatusCode::BAD_REQUEST,
+            format!("rejection: invalid header {}: {}", T::name(), err),
+        )
+    })
+}
+
+impl<S, T> FromRequestParts<S> for Header<T>
+where
+    T: TypedHeader,
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match parts.headers.get(T::name()) {
+            Some(value) => decode(value).map(Self),
+            None => Err(rest_rejection(
+                "header",
+                StatusCode::BAD_REQUEST,
+                format!("rejection: missing header {}", T::name()),
+            )),
+        }
+    }
+}
+
+impl<S, T> OptionalFromRequestParts<S> for Header<T>
+where
+    T: TypedHeader,
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Option<Self>, Self::Rejection> {
+        match parts.headers.get(T::name()) {
+            Some(value) => decode(value).map(|value| Some(Self(value))),
+            None => Ok(None),
+        }
+    }
+}
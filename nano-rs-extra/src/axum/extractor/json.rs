@@ -1,26 +1,22 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
 use crate::axum::extractor::Json;
-use crate::axum::rest::RestResp;
 use axum::extract::rejection::JsonRejection;
 use axum::extract::{FromRequest, Request};
-use axum::http::StatusCode;
 
 impl<S, T> FromRequest<S> for Json<T>
 where
     axum::Json<T>: FromRequest<S, Rejection = JsonRejection>,
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, axum::Json<RestResp<()>>);
+    type Rejection = RestRejection;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
         match axum::Json::<T>::from_request(req, state).await {
             Ok(value) => Ok(Self(value.0)),
-            Err(rejection) => Err((
+            Err(rejection) => Err(rest_rejection(
+                "json",
                 rejection.status(),
-                axum::Json(RestResp::<()> {
-                    code: 500,
-                    msg: format!("rejection: {}", rejection.body_text()),
-                    data: None,
-                }),
+                format!("rejection: {}", rejection.body_text()),
             )),
         }
     }
@@ -1,5 +1,31 @@
-pub mod path;
+pub mod claims;
+pub mod form;
+pub mod header;
 pub mod json;
+#[cfg(feature = "multipart")]
+pub mod multipart;
+pub mod path;
+pub mod query;
+pub mod rejection;
+pub mod session;
+pub mod valid;
+
+#[cfg(feature = "multipart")]
+pub use multipart::Multipart;
+
 pub struct Json<T>(pub T);
 
-pub struct Path<T>(pub T);
\ No newline at end of file
+pub struct Path<T>(pub T);
+
+pub struct Query<T>(pub T);
+
+pub struct Form<T>(pub T);
+
+/// claims of the verified jwt, inserted by `middleware::auth::require_auth`
+pub struct Claims<T>(pub T);
+
+/// typed request header, see `header::TypedHeader`
+pub struct Header<T>(pub T);
+
+/// extractor validated with `valid::Validate` after deserialization, e.g. `Valid<Json<T>>`
+pub struct Valid<E>(pub E);
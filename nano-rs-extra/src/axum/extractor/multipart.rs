@@ -0,0 +1,38 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use axum::extract::{FromRequest, Request};
+
+/// multipart form extractor with `RestResp` rejections, fields are read with `next_field`
+pub struct Multipart(pub axum::extract::Multipart);
+
+impl<S> FromRequest<S> for Multipart
+where
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Multipart::from_request(req, state).await {
+            Ok(value) => Ok(Self(value)),
+            Err(rejection) => Err(rest_rejection(
+                "multipart",
+                rejection.status(),
+                format!("rejection: {}", rejection.body_text()),
+            )),
+        }
+    }
+}
+
+impl Multipart {
+    /// next field of the form, a broken body is rejected as `RestResp`
+    pub async fn next_field(
+        &mut self,
+    ) -> Result<Option<axum::extract::multipart::Field<'_>>, RestRejection> {
+        self.0.next_field().await.map_err(|err| {
+            rest_rejection(
+                "multipart",
+                err.status(),
+                format!("rejection: {}", err.body_text()),
+            )
+        })
+    }
+}
@@ -1,5 +1,5 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
 use crate::axum::extractor::Path;
-use crate::axum::rest::RestResp;
 use axum::extract::path::ErrorKind;
 use axum::extract::rejection::PathRejection;
 use axum::extract::FromRequestParts;
@@ -12,93 +12,44 @@ where
     T: DeserializeOwned + Send,
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, axum::Json<RestResp<()>>);
+    type Rejection = RestRejection;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         match axum::extract::Path::<T>::from_request_parts(parts, state).await {
             Ok(value) => Ok(Self(value.0)),
             Err(rejection) => {
-                let (status, body) = match rejection {
+                let (status, msg) = match rejection {
                     PathRejection::FailedToDeserializePathParams(inner) => {
-                        let mut status = StatusCode::BAD_REQUEST;
-
                         let kind = inner.into_kind();
-                        let body = match &kind {
-                            ErrorKind::WrongNumberOfParameters { .. } => RestResp::<()> {
-                                code: 500,
-                                msg: kind.to_string(),
-                                data: None,
-                            },
-
-                            ErrorKind::ParseErrorAtKey { .. } => RestResp::<()> {
-                                code: 500,
-                                msg: kind.to_string(),
-                                data: None,
-                            },
-
-                            ErrorKind::ParseErrorAtIndex { .. } => RestResp::<()> {
-                                code: 500,
-                                msg: kind.to_string(),
-                                data: None,
-                            },
-
-                            ErrorKind::ParseError { .. } => RestResp::<()> {
-                                code: 500,
-                                msg: kind.to_string(),
-                                data: None,
-                            },
-
-                            ErrorKind::InvalidUtf8InPathParam { key: _key } => RestResp::<()> {
-                                code: 500,
-                                msg: kind.to_string(),
-                                data: None,
-                            },
-
+                        match &kind {
+                            ErrorKind::WrongNumberOfParameters { .. }
+                            | ErrorKind::ParseErrorAtKey { .. }
+                            | ErrorKind::ParseErrorAtIndex { .. }
+                            | ErrorKind::ParseError { .. }
+                            | ErrorKind::InvalidUtf8InPathParam { .. } => {
+                                (StatusCode::BAD_REQUEST, kind.to_string())
+                            }
+                            // this error is caused by the programmer using an unsupported type
+                            // (such as nested maps) so respond with `500` instead
                             ErrorKind::UnsupportedType { .. } => {
-                                // this error is caused by the programmer using an unsupported type
-                                // (such as nested maps) so respond with `500` instead
-                                status = StatusCode::INTERNAL_SERVER_ERROR;
-                                RestResp::<()> {
-                                    code: 500,
-                                    msg: kind.to_string(),
-                                    data: None,
-                                }
+                                (StatusCode::INTERNAL_SERVER_ERROR, kind.to_string())
                             }
-
-                            ErrorKind::Message(msg) => RestResp::<()> {
-                                code: 500,
-                                msg: msg.clone(),
-                                data: None,
-                            },
-
-                            _ => RestResp::<()> {
-                                code: 500,
-                                msg: format!("Unhandled deserialization error: {kind}"),
-                                data: None,
-                            },
-                        };
-
-                        (status, body)
+                            ErrorKind::Message(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+                            _ => (
+                                StatusCode::BAD_REQUEST,
+                                format!("Unhandled deserialization error: {kind}"),
+                            ),
+                        }
+                    }
+                    PathRejection::MissingPathParams(error) => {
+                        (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
                     }
-                    PathRejection::MissingPathParams(error) => (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        RestResp::<()> {
-                            code: 500,
-                            msg: error.to_string(),
-                            data: None,
-                        },
-                    ),
                     _ => (
                         StatusCode::INTERNAL_SERVER_ERROR,
-                        RestResp::<()> {
-                            code: 500,
-                            msg: format!("Unhandled path rejection: {rejection}"),
-                            data: None,
-                        },
+                        format!("Unhandled path rejection: {rejection}"),
                     ),
                 };
-
-                Err((status, axum::Json(body)))
+                Err(rest_rejection("path", status, msg))
             }
         }
     }
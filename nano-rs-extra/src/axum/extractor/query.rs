@@ -0,0 +1,24 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use crate::axum::extractor::Query;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Query::<T>::from_request_parts(parts, state).await {
+            Ok(value) => Ok(Self(value.0)),
+            Err(rejection) => Err(rest_rejection(
+                "query",
+                rejection.status(),
+                format!("rejection: {}", rejection.body_text()),
+            )),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::{Arc, OnceLock};
+
+use axum::http::StatusCode;
+
+use crate::axum::i18n;
+use crate::axum::rest::RestResp;
+
+/// rejection of nano extractors, a `RestResp` whose `status` is the rejection status
+pub type RestRejection = RestResp<()>;
+
+/// why a nano extractor rejected the request
+#[derive(Debug, Clone)]
+pub struct ExtractRejection {
+    /// extractor name, json, form, query, path, header, multipart, page, claims or valid
+    pub extractor: &'static str,
+    /// 400 malformed input, 413 body too large, 415 wrong content type, 422 failed validation,
+    /// 5xx server side errors
+    pub status: StatusCode,
+    pub msg: String,
+}
+
+impl ExtractRejection {
+    /// i18n message key, e.g. rejection.json, `{detail}` is the original message
+    pub fn key(&self) -> String {
+        format!("rejection.{}", self.extractor)
+    }
+
+    /// message in the request locale when a catalog has `key`, else the original message
+    pub fn localized_msg(&self) -> String {
+        i18n::lookup(self.key().as_str(), &[("detail".to_string(), self.msg.clone())])
+            .unwrap_or(self.msg.clone())
+    }
+}
+
+/// 参数解析失败处理
+/// Build the `RestResp` of extractor rejections, set it with `set_rejection_handler`
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use nano_rs_extra::axum::extractor::rejection::{set_rejection_handler, ExtractRejection, RejectionHandler};
+/// use nano_rs_extra::axum::rest::RestResp;
+///
+/// struct BizCodeRejection;
+///
+/// impl RejectionHandler for BizCodeRejection {
+///     fn handle(&self, rejection: &ExtractRejection) -> RestResp<()> {
+///         RestResp {
+///             code: 10000 + rejection.status.as_u16() as i32,
+///             msg: format!("bad {}: {}", rejection.extractor, rejection.msg),
+///             data: None,
+///             status: None,
+///         }
+///     }
+/// }
+///
+/// set_rejection_handler(Arc::new(BizCodeRejection)).unwrap();
+/// ```
+pub trait RejectionHandler: Send + Sync {
+    /// body of the rejection, its `status` is always set to `rejection.status`
+    fn handle(&self, rejection: &ExtractRejection) -> RestResp<()>;
+}
+
+/// body code mirrors the http status, e.g. 400, 413, 415, msg is localized by `ExtractRejection::key`
+pub struct DefaultRejectionHandler;
+
+impl RejectionHandler for DefaultRejectionHandler {
+    fn handle(&self, rejection: &ExtractRejection) -> RestResp<()> {
+        RestResp {
+            code: rejection.status.as_u16() as i32,
+            msg: rejection.localized_msg(),
+            data: None,
+            status: None,
+        }
+    }
+}
+
+static REJECTION_HANDLER: OnceLock<Arc<dyn RejectionHandler>> = OnceLock::new();
+
+/// set handler used by all nano extractors, call it before serving requests
+pub fn set_rejection_handler(handler: Arc<dyn RejectionHandler>) -> Result<(), String> {
+    REJECTION_HANDLER
+        .set(handler)
+        .map_err(|_| "rejection handler already set".to_string())
+}
+
+/// rejection handler, `DefaultRejectionHandler` unless `set_rejection_handler` was called
+pub fn rejection_handler() -> Arc<dyn RejectionHandler> {
+    REJECTION_HANDLER
+        .get_or_init(|| Arc::new(DefaultRejectionHandler))
+        .clone()
+}
+
+/// build the `RestResp` rejection shared by all nano extractors
+pub fn rest_rejection(extractor: &'static str, status: StatusCode, msg: String) -> RestRejection {
+    let rejection = ExtractRejection {
+        extractor,
+        status,
+        msg,
+    };
+    let mut body = rejection_handler().handle(&rejection);
+    body.status = Some(status);
+    body
+}
@@ -0,0 +1,22 @@
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use crate::axum::session::Session;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+
+impl<S> FromRequestParts<S> for Session
+where
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Session>().cloned().ok_or_else(|| {
+            rest_rejection(
+                "session",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "rejection: missing session, session is not configured".to_string(),
+            )
+        })
+    }
+}
@@ -0,0 +1,209 @@
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::axum::extractor::rejection::rest_rejection;
+use crate::axum::extractor::{Form, Json, Path, Query, Valid};
+use crate::axum::rest::RestResp;
+
+/// 参数校验
+/// Input validation, run by the `Valid` extractor after deserialization
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::errors::ServerError;
+/// use nano_rs_extra::axum::extractor::valid::{Validate, ValidationErrors};
+/// use nano_rs_extra::axum::extractor::{Json, Valid};
+/// use nano_rs_extra::axum::rest::{biz_ok, RestResp};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// pub struct NewPet {
+///     pub name: String,
+///     pub age: i64,
+/// }
+///
+/// impl Validate for NewPet {
+///     fn validate(&self) -> Result<(), ValidationErrors> {
+///         ValidationErrors::new()
+///             .check(!self.name.is_empty(), "name", "must not be empty")
+///             .check((0..=30).contains(&self.age), "age", "must be between 0 and 30")
+///             .into_result()
+///     }
+/// }
+///
+/// pub async fn add_pet(Valid(Json(pet)): Valid<Json<NewPet>>) -> Result<RestResp<String>, ServerError> {
+///     biz_ok(200, pet.name)
+/// }
+/// ```
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// a failing field and the reason
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub reason: String,
+}
+
+/// failing fields of a `Validate` run, sent as `RestResp.data` with 422, code and msg come from
+/// `rejection_handler()` like other rejections (extractor "valid", i18n key `rejection.valid`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ValidationErrors(pub Vec<FieldError>);
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        ValidationErrors::default()
+    }
+
+    pub fn add(mut self, field: impl Into<String>, reason: impl Into<String>) -> Self {
+        self.0.push(FieldError {
+            field: field.into(),
+            reason: reason.into(),
+        });
+        self
+    }
+
+    /// add the field error when `valid` is false
+    pub fn check(self, valid: bool, field: impl Into<String>, reason: impl Into<String>) -> Self {
+        if valid {
+            self
+        } else {
+            self.add(field, reason)
+        }
+    }
+
+    /// merge errors of a nested value, fields are prefixed with `field.`
+    pub fn nested(mut self, field: &str, result: Result<(), ValidationErrors>) -> Self {
+        if let Err(errors) = result {
+            self.0.extend(errors.0.into_iter().map(|error| FieldError {
+                field: format!("{}.{}", field, error.field),
+                reason: error.reason,
+            }));
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// `Ok` when no field failed
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl IntoResponse for ValidationErrors {
+    fn into_response(self) -> Response {
+        let msg = self
+            .0
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.reason))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let rejection = rest_rejection("valid", StatusCode::UNPROCESSABLE_ENTITY, msg);
+        RestResp {
+            code: rejection.code,
+            msg: rejection.msg,
+            data: Some(self),
+            status: rejection.status,
+        }
+        .into_response()
+    }
+}
+
+/// extractor whose value is validated, `Valid<Json<T>>`, `Valid<Query<T>>` etc..
+pub trait ValidInner {
+    type Inner: Validate;
+
+    fn inner(&self) -> &Self::Inner;
+}
+
+macro_rules! impl_valid_inner {
+    ($($extractor:ident),*) => {
+        $(
+            impl<T: Validate> ValidInner for $extractor<T> {
+                type Inner = T;
+
+                fn inner(&self) -> &T {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+impl_valid_inner!(Json, Form, Query, Path);
+
+impl<S, E> FromRequest<S> for Valid<E>
+where
+    E: FromRequest<S> + ValidInner,
+    E::Rejection: IntoResponse,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let value = E::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        value.inner().validate().map_err(IntoResponse::into_response)?;
+        Ok(Valid(value))
+    }
+}
+
+impl<S, E> FromRequestParts<S> for Valid<E>
+where
+    E: FromRequestParts<S> + ValidInner,
+    E::Rejection: IntoResponse,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = E::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        value.inner().validate().map_err(IntoResponse::into_response)?;
+        Ok(Valid(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn rejection_keeps_fields() {
+        let errors = ValidationErrors::new()
+            .add("name", "must not be empty")
+            .nested(
+                "owner",
+                Err(ValidationErrors::new().add("age", "too young")),
+            );
+        let resp = errors.into_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Value =
+            serde_json::from_slice(&to_bytes(resp.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body["code"], 422);
+        assert_eq!(body["msg"], "name: must not be empty; owner.age: too young");
+        assert_eq!(
+            body["data"],
+            json!([
+                {"field": "name", "reason": "must not be empty"},
+                {"field": "owner.age", "reason": "too young"}
+            ])
+        );
+    }
+}
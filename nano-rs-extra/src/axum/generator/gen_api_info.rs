@@ -2,14 +2,12 @@
 use crate::axum::generator::parse_utoipa_info;
 use quote::__private::TokenStream;
 use quote::quote;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
-use syn::{parse_quote, Attribute, FnArg, ItemUse};
+use syn::parse_quote;
 
-use nano_rs_build::api_fn::ApiFn;
+use nano_rs_build::api_fn::ApiFns;
 use nano_rs_build::api_gen::GenApiInfo;
 
 pub struct AxumGenApiInfo {}
@@ -18,10 +16,7 @@ impl GenApiInfo for AxumGenApiInfo {
     fn gen_api_info(
         &self,
         path_buf: PathBuf,
-        api_fns: HashMap<
-            String,
-            ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
-        >,
+        api_fns: ApiFns,
     ) {
         eprintln!("gen_api_info in {:?}", path_buf);
         let api_info = path_buf.join(self.get_api_info_file_path());
@@ -50,6 +45,11 @@ impl GenApiInfo for AxumGenApiInfo {
                 group_name = api_doc.api_group;
             }
             let public = api_fn.public;
+            let permission = match api_fn.permission {
+                Some(permission) => quote!(Some(#permission.to_string())),
+                None => quote!(None),
+            };
+            let roles = api_fn.roles.unwrap_or_default();
             api_info_vec.push(quote! {
                 ApiInfo {
                     method: #method.to_string(),
@@ -58,6 +58,8 @@ impl GenApiInfo for AxumGenApiInfo {
                     handler_fun: #handler_fun.to_string(),
                     summary: #summary.to_string(),
                     public: #public,
+                    permission: #permission,
+                    roles: vec![#(#roles.to_string()),*],
                     group_name: #group_name.to_string(),
                 }
             });
@@ -94,7 +96,7 @@ impl AxumGenApiInfo {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiInfo {
     pub method: String,
     pub path: String,
@@ -102,5 +104,24 @@ pub struct ApiInfo {
     pub handler_fun: String,
     pub summary: String,
     pub public: bool,
+    /// permission required by the route, e.g. "pet:write"
+    #[serde(default)]
+    pub permission: Option<String>,
+    /// roles allowed to call the route, any of them
+    #[serde(default)]
+    pub roles: Vec<String>,
     pub group_name: String,
 }
+
+impl ApiInfo {
+    /// prepend base path (e.g. `RestConfig.base_path`) to `base_path`
+    pub fn with_base_path(mut self, base_path: &str) -> Self {
+        self.base_path = format!("{}{}", base_path.trim_end_matches('/'), self.base_path);
+        self
+    }
+
+    /// full request path, `base_path` + `path`
+    pub fn full_path(&self) -> String {
+        format!("{}{}", self.base_path, self.path)
+    }
+}
@@ -0,0 +1,404 @@
+#[cfg(feature = "utoipa_axum")]
+use crate::axum::generator::parse_utoipa_info;
+use crate::axum::generator::AxumGen;
+use nano_rs_build::api_fn::{ApiFns, SynApiFn};
+use nano_rs_build::api_gen::GenClient;
+use quote::__private::{Span, TokenStream};
+use quote::quote;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use syn::{
+    parse_quote, parse_str, FnArg, GenericArgument, Ident, ItemUse, Pat, Path,
+    PathArguments, Type,
+};
+
+/// types usable without a `use`
+const PRELUDE_TYPES: [&str; 21] = [
+    "String", "Vec", "Option", "Box", "bool", "char", "str", "i8", "i16", "i32", "i64", "i128",
+    "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InputKind {
+    Path,
+    Query,
+    Json,
+    Form,
+}
+
+/// 客户端生成
+/// Generate `src/client.rs`, an `ApiClient` with one async method per route
+///
+/// methods take the `Path`, `Query` (`PageQuery`) and `Json`/`Form` inputs of the handler (`Valid`
+/// unwrapped) and return its `RestResp<T>`, handlers with `Multipart` or without a `RestResp`
+/// return type are skipped. method names are the handler names, qualified by the module
+/// when two handlers share one
+pub struct AxumGenClient {
+    pub client_name: String,
+}
+
+impl GenClient for AxumGenClient {
+    fn gen_client(
+        &self,
+        path_buf: PathBuf,
+        api_fns: ApiFns,
+    ) {
+        eprintln!("AxumGenClient gen_client in {:?}", path_buf);
+        let client = path_buf.join(self.get_client_file_path());
+        #[cfg(feature = "utoipa_axum")]
+        let mut api_fns = api_fns;
+        #[cfg(feature = "utoipa_axum")]
+        for (_name, api_fn) in api_fns.iter_mut() {
+            parse_utoipa_info(api_fn);
+        }
+        let mut name_count: HashMap<String, usize> = HashMap::new();
+        for api_fn in api_fns.values() {
+            *name_count.entry(api_fn.api_fn_name.clone()).or_default() += 1;
+        }
+        let mut methods_code: Vec<TokenStream> = vec![];
+        let mut keys: Vec<_> = api_fns.keys().collect();
+        keys.sort();
+        for key in keys {
+            let api_fn = api_fns.get(key).unwrap();
+            let method_name = if name_count.get(&api_fn.api_fn_name).copied().unwrap_or(0) > 1 {
+                key.trim_start_matches("crate::").replace("::", "_")
+            } else {
+                api_fn.api_fn_name.clone()
+            };
+            if let Some(method_code) = self.gen_method(key, method_name.as_str(), api_fn) {
+                methods_code.push(method_code);
+            }
+        }
+        let client_ident = Ident::new(self.client_name.as_str(), Span::call_site());
+        let client_code: TokenStream = parse_quote!(
+            /// Code generated by nano-rs. DO NOT EDIT.
+            use axum::http::Method;
+            use nano_rs::axum::client::{ClientError, ClientRequest, RestClient};
+            use nano_rs::axum::rest::RestResp;
+
+            #[allow(dead_code)]
+            #[derive(Clone)]
+            pub struct #client_ident {
+                pub client: RestClient,
+            }
+
+            #[allow(dead_code)]
+            impl #client_ident {
+                pub fn new(client: RestClient) -> Self {
+                    #client_ident { client }
+                }
+
+                #(#methods_code)*
+            }
+        );
+
+        let syntax_tree = syn::parse_file(client_code.to_string().as_str()).unwrap();
+        let formatted = prettyplease::unparse(&syntax_tree);
+        fs::write(client.as_path(), formatted).expect("create file failed");
+    }
+}
+
+impl AxumGenClient {
+    pub fn new() -> Self {
+        AxumGenClient {
+            client_name: "ApiClient".to_string(),
+        }
+    }
+
+    /// name of the generated client struct, default "ApiClient"
+    pub fn set_client_name(mut self, client_name: &str) -> Self {
+        self.client_name = client_name.to_string();
+        self
+    }
+
+    fn gen_method(
+        &self,
+        key: &str,
+        method_name: &str,
+        api_fn: &SynApiFn,
+    ) -> Option<TokenStream> {
+        let module = key
+            .rsplit_once("::")
+            .map(|(module, _)| module)
+            .unwrap_or("crate");
+        let uses = api_fn.use_crate.clone().unwrap_or_default();
+        let Some(data_type) = api_fn
+            .output
+            .as_ref()
+            .and_then(|output| parse_str::<Type>(output).ok())
+            .and_then(|output| self.rest_resp_data(&output))
+        else {
+            eprintln!("client skip {}: not returning RestResp", key);
+            return None;
+        };
+        let data_type = self.qualify_type(&data_type, module, &uses);
+
+        let mut params = vec![];
+        let mut request_code = vec![];
+        let mut names: Vec<String> = vec![];
+        for arg in api_fn.inputs.iter().flatten() {
+            let FnArg::Typed(pat_type) = arg else {
+                continue;
+            };
+            if self.last_ident(&pat_type.ty).as_deref() == Some("Multipart") {
+                eprintln!("client skip {}: multipart body", key);
+                return None;
+            }
+            let Some((kind, ty)) = self.input_kind(&pat_type.ty) else {
+                continue;
+            };
+            let mut name = self.binding_name(&pat_type.pat).unwrap_or(match kind {
+                InputKind::Path => "path".to_string(),
+                InputKind::Query => "query".to_string(),
+                InputKind::Json | InputKind::Form => "body".to_string(),
+            });
+            if names.contains(&name) {
+                name = format!("{}_{}", name, names.len());
+            }
+            names.push(name.clone());
+            let ident = Ident::new(name.as_str(), Span::call_site());
+            let ty = self.qualify_type(&ty, module, &uses);
+            if self.last_ident(&ty).as_deref() == Some("String") {
+                params.push(quote!(#ident: &str));
+            } else {
+                params.push(quote!(#ident: &#ty));
+            }
+            request_code.push(match kind {
+                InputKind::Path => quote!(.path(#ident)?),
+                InputKind::Query => quote!(.query(#ident)?),
+                InputKind::Json => quote!(.json(#ident)?),
+                InputKind::Form => quote!(.form(#ident)?),
+            });
+        }
+
+        let method_ident = Ident::new(method_name, Span::call_site());
+        let http_method = Ident::new(api_fn.method.to_uppercase().as_str(), Span::call_site());
+        let path = api_fn.path.as_str();
+        let mut docs = vec![];
+        if let Some(api_fn_doc) = api_fn.api_fn_doc.as_ref() {
+            let summary = api_fn_doc.api.as_str();
+            docs.push(quote!(#[doc = #summary]));
+        }
+        let route = format!(" `{} {}`", api_fn.method.to_uppercase(), path);
+        docs.push(quote!(#[doc = #route]));
+        Some(quote!(
+            #(#docs)*
+            pub async fn #method_ident(&self, #(#params),*) -> Result<RestResp<#data_type>, ClientError> {
+                let req = ClientRequest::new(Method::#http_method, #path)#(#request_code)*;
+                self.client.send(req).await
+            }
+        ))
+    }
+
+    /// `T` of `Result<RestResp<T>, E>` or `RestResp<T>`
+    fn rest_resp_data(&self, output: &Type) -> Option<Type> {
+        match self.last_ident(output)?.as_str() {
+            "Result" => self.rest_resp_data(&self.generic_type(output)?),
+            "RestResp" => self.generic_type(output),
+            _ => None,
+        }
+    }
+
+    /// client input of an extractor type, `Valid<E>` is unwrapped
+    fn input_kind(&self, ty: &Type) -> Option<(InputKind, Type)> {
+        match self.last_ident(ty)?.as_str() {
+            "Valid" => self.input_kind(&self.generic_type(ty)?),
+            "Path" => Some((InputKind::Path, self.generic_type(ty)?)),
+            "Query" => Some((InputKind::Query, self.generic_type(ty)?)),
+            "PageQuery" => Some((InputKind::Query, ty.clone())),
+            "Json" => Some((InputKind::Json, self.generic_type(ty)?)),
+            "Form" => Some((InputKind::Form, self.generic_type(ty)?)),
+            _ => None,
+        }
+    }
+
+    fn last_ident(&self, ty: &Type) -> Option<String> {
+        match ty {
+            Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string()),
+            _ => None,
+        }
+    }
+
+    /// first generic type argument of the last path segment
+    fn generic_type(&self, ty: &Type) -> Option<Type> {
+        let Type::Path(type_path) = ty else {
+            return None;
+        };
+        let PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        })
+    }
+
+    /// `name` of `Path(name)`, `Json(_pet)` or `page: PageQuery`
+    fn binding_name(&self, pat: &Pat) -> Option<String> {
+        let name = match pat {
+            Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+            Pat::TupleStruct(pat_tuple_struct) => match pat_tuple_struct.elems.first()? {
+                Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let name = name.trim_start_matches('_').to_string();
+        (!name.is_empty()).then_some(name)
+    }
+
+    /// make the handler module types usable from the client module
+    ///
+    /// single segment types are resolved by the `use` of the handler, else they are local to its
+    /// module. longer paths are kept unless they start with `self`, `super` or a `use`d name
+    fn qualify_type(&self, ty: &Type, module: &str, uses: &Vec<ItemUse>) -> Type {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let mut type_path = type_path.clone();
+                for segment in type_path.path.segments.iter_mut() {
+                    if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                        for arg in args.args.iter_mut() {
+                            if let GenericArgument::Type(arg_ty) = arg {
+                                *arg_ty = self.qualify_type(arg_ty, module, uses);
+                            }
+                        }
+                    }
+                }
+                let first = type_path.path.segments.first().unwrap().ident.to_string();
+                let prefix = match first.as_str() {
+                    "crate" => return Type::Path(type_path),
+                    "self" => Some(module.to_string()),
+                    "super" => Some(
+                        module
+                            .rsplit_once("::")
+                            .map(|(parent, _)| parent)
+                            .unwrap_or("crate")
+                            .to_string(),
+                    ),
+                    _ if type_path.path.segments.len() == 1
+                        && PRELUDE_TYPES.contains(&first.as_str()) =>
+                    {
+                        None
+                    }
+                    _ => match self.get_full_crate_name(first.clone(), uses) {
+                        Some(full_name) => {
+                            let full_name = self.qualify_path(full_name.as_str(), module);
+                            Some(
+                                full_name
+                                    .rsplit_once("::")
+                                    .map(|(parent, _)| parent.to_string())
+                                    .unwrap_or_default(),
+                            )
+                        }
+                        // a local type of the handler module
+                        None if type_path.path.segments.len() == 1 => Some(module.to_string()),
+                        // external crates and fully qualified paths are kept
+                        None => None,
+                    },
+                };
+                let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) else {
+                    return Type::Path(type_path);
+                };
+                let skip = usize::from(first == "self" || first == "super");
+                let segments: Vec<_> = type_path.path.segments.iter().skip(skip).collect();
+                let prefix: Path = parse_str(prefix.as_str()).expect("Failed to parse type path");
+                Type::Path(parse_quote!(#prefix::#(#segments)::*))
+            }
+            Type::Reference(type_reference) => {
+                let mut type_reference = type_reference.clone();
+                type_reference.elem =
+                    Box::new(self.qualify_type(&type_reference.elem, module, uses));
+                Type::Reference(type_reference)
+            }
+            Type::Tuple(type_tuple) => {
+                let mut type_tuple = type_tuple.clone();
+                for elem in type_tuple.elems.iter_mut() {
+                    *elem = self.qualify_type(elem, module, uses);
+                }
+                Type::Tuple(type_tuple)
+            }
+            Type::Slice(type_slice) => {
+                let mut type_slice = type_slice.clone();
+                type_slice.elem = Box::new(self.qualify_type(&type_slice.elem, module, uses));
+                Type::Slice(type_slice)
+            }
+            _ => ty.clone(),
+        }
+    }
+
+    /// `super::` and `self::` of a `use` are relative to the handler module
+    fn qualify_path(&self, path: &str, module: &str) -> String {
+        if let Some(rest) = path.strip_prefix("self::") {
+            format!("{}::{}", module, rest)
+        } else if let Some(rest) = path.strip_prefix("super::") {
+            let parent = module
+                .rsplit_once("::")
+                .map(|(parent, _)| parent)
+                .unwrap_or("crate");
+            format!("{}::{}", parent, rest)
+        } else {
+            path.to_string()
+        }
+    }
+}
+
+impl Default for AxumGenClient {
+    fn default() -> Self {
+        AxumGenClient::new()
+    }
+}
+
+impl AxumGen for AxumGenClient {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+
+    fn qualify(ty: &str) -> String {
+        let uses: Vec<ItemUse> = vec![
+            parse_quote!(use crate::model::pet::Pet;),
+            parse_quote!(use nano_rs::axum::page;),
+            parse_quote!(use super::types::Shower;),
+        ];
+        let ty: Type = parse_str(ty).unwrap();
+        AxumGenClient::new()
+            .qualify_type(&ty, "crate::api::store", &uses)
+            .to_token_stream()
+            .to_string()
+            .replace(' ', "")
+    }
+
+    #[test]
+    fn qualify_handler_types() {
+        assert_eq!(qualify("String"), "String");
+        assert_eq!(qualify("Vec<Pet>"), "Vec<crate::model::pet::Pet>");
+        assert_eq!(qualify("Store"), "crate::api::store::Store");
+        assert_eq!(qualify("Shower"), "crate::api::types::Shower");
+        assert_eq!(qualify("self::Store"), "crate::api::store::Store");
+        assert_eq!(qualify("super::Store"), "crate::api::Store");
+        assert_eq!(qualify("crate::model::Store"), "crate::model::Store");
+        assert_eq!(
+            qualify("page::PagedResp<Pet>"),
+            "nano_rs::axum::page::PagedResp<crate::model::pet::Pet>"
+        );
+    }
+
+    #[test]
+    fn keep_external_paths() {
+        assert_eq!(qualify("serde_json::Value"), "serde_json::Value");
+        assert_eq!(
+            qualify("std::collections::HashMap<String, Pet>"),
+            "std::collections::HashMap<String,crate::model::pet::Pet>"
+        );
+        assert_eq!(
+            qualify("nano_rs::axum::page::PagedResp<Store>"),
+            "nano_rs::axum::page::PagedResp<crate::api::store::Store>"
+        );
+    }
+}
@@ -2,19 +2,24 @@ use quote::{quote, ToTokens};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
 use syn::{
-    parse_str, Attribute, FnArg, Item, ItemEnum, ItemMod, ItemStruct, ItemUse, Meta, TypePath,
+    parse_str, Item, ItemEnum, ItemMod, ItemStruct, ItemUse, Meta, TypePath,
 };
+use utoipa::openapi::extensions::ExtensionsBuilder;
+use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type};
 use utoipa::openapi::{
-    Contact, ExternalDocs, Info, License, Object, SecurityRequirement, Server, Tag,
+    Contact, ExternalDocs, Info, License, Object, OpenApi, RefOr, Schema, SecurityRequirement,
+    Server, Tag,
 };
 
-use nano_rs_build::api_fn::ApiFn;
+use nano_rs_build::api_fn::{ApiFns, SynApiFn};
 use nano_rs_build::api_gen::GenDoc;
+use nano_rs_core::config::rest::{ResponseFormat, RestConfig};
 
+use crate::axum::errors::BizErrorInfo;
 use crate::axum::generator::AxumGen;
+use crate::axum::problem::APPLICATION_PROBLEM_JSON;
+use crate::axum::rest::Envelope;
 
 pub struct AxumGenDoc {
     pub info: Info,
@@ -30,10 +35,7 @@ impl GenDoc for AxumGenDoc {
         &self,
         rs_files: Vec<PathBuf>,
         path_buf: PathBuf,
-        api_fns: HashMap<
-            String,
-            ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
-        >,
+        api_fns: ApiFns,
     ) {
         eprintln!("AxumGenRoute gen_doc in {:?}", path_buf);
         let mut struct_map: HashMap<String, ItemStruct> = HashMap::new();
@@ -163,6 +165,18 @@ impl GenDoc for AxumGenDoc {
                 )
             )]
             pub struct GenApi{}
+
+            impl GenApi {
+                /// openapi of the routes as served with `rest_config` (base path, envelope and
+                /// response format), prefer it over `GenApi::openapi()`
+                pub fn openapi_with_config(
+                    rest_config: &nano_rs::config::rest::RestConfig,
+                ) -> utoipa::openapi::OpenApi {
+                    let mut openapi = <GenApi as OpenApi>::openapi();
+                    nano_rs::axum::generator::gen_doc::apply_rest_config(&mut openapi, rest_config);
+                    openapi
+                }
+            }
         };
         let syntax_tree = syn::parse_file(doc_code.to_string().as_str()).unwrap();
         let formatted = prettyplease::unparse(&syntax_tree);
@@ -180,6 +194,234 @@ impl GenDoc for AxumGenDoc {
     }
 }
 
+/// prepend base path (e.g. `RestConfig.base_path`) to the servers of generated openapi,
+/// a server with the base path is added when there is none
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::generator::gen_doc::apply_base_path;
+/// use utoipa::openapi::OpenApiBuilder;
+///
+/// let mut openapi = OpenApiBuilder::new().build();
+/// apply_base_path(&mut openapi, Some("/v1"));
+/// assert_eq!(openapi.servers.unwrap()[0].url, "/v1");
+/// ```
+pub fn apply_base_path(openapi: &mut OpenApi, base_path: Option<&str>) {
+    let Some(base_path) = base_path else {
+        return;
+    };
+    match openapi.servers.as_mut() {
+        Some(servers) if !servers.is_empty() => {
+            for server in servers.iter_mut() {
+                server.url = format!("{}{}", server.url.trim_end_matches('/'), base_path);
+            }
+        }
+        _ => openapi.servers = Some(vec![Server::new(base_path)]),
+    }
+}
+
+/// list business error codes (`BizError::all()`) in the generated openapi,
+/// as a table in `info.description` and as `x-biz-errors` of info
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::errors::BizErrorInfo;
+/// use nano_rs_extra::axum::generator::gen_doc::apply_biz_errors;
+/// use utoipa::openapi::OpenApiBuilder;
+///
+/// let mut openapi = OpenApiBuilder::new().build();
+/// // PetError::all() of a `#[derive(BizError)]` enum
+/// let errors = vec![BizErrorInfo { code: 40001, msg: "pet not found".to_string(), status: 404 }];
+/// apply_biz_errors(&mut openapi, &errors);
+/// assert!(openapi.info.description.unwrap().contains("40001"));
+/// ```
+pub fn apply_biz_errors(openapi: &mut OpenApi, errors: &[BizErrorInfo]) {
+    if errors.is_empty() {
+        return;
+    }
+    let mut errors = errors.to_vec();
+    errors.sort_by_key(|error| error.code);
+    errors.dedup_by_key(|error| error.code);
+    let mut table = String::from("## Error Codes\n\n| code | msg | http status |\n| --- | --- | --- |\n");
+    for error in errors.iter() {
+        table.push_str(&format!("| {} | {} | {} |\n", error.code, error.msg, error.status));
+    }
+    openapi.info.description = Some(match openapi.info.description.take() {
+        Some(description) => format!("{}\n\n{}", description, table),
+        None => table,
+    });
+    let value = serde_json::to_value(&errors).unwrap_or_default();
+    openapi
+        .info
+        .extensions
+        .get_or_insert_with(Default::default)
+        .merge(ExtensionsBuilder::new().add("x-biz-errors", value).build());
+}
+
+/// rewrite the bodies of the generated openapi as the server sends them with `rest_config`:
+/// `base_path`, `envelope` and `response_format`, used by the generated `GenApi::openapi_with_config`
+///
+/// # Example
+/// ```rust
+/// use nano_rs_core::config::rest::RestConfig;
+/// use nano_rs_extra::axum::generator::gen_doc::apply_rest_config;
+/// use utoipa::openapi::OpenApiBuilder;
+///
+/// let mut openapi = OpenApiBuilder::new().build();
+/// apply_rest_config(&mut openapi, &RestConfig::default());
+/// ```
+pub fn apply_rest_config(openapi: &mut OpenApi, rest_config: &RestConfig) {
+    apply_base_path(openapi, rest_config.get_base_path().as_deref());
+    apply_response_format(
+        openapi,
+        rest_config.response_format.unwrap_or_default(),
+        &Envelope::from(&rest_config.envelope.clone().unwrap_or_default()),
+    );
+}
+
+/// wrap the json and text bodies of the generated openapi in the `RestResp` envelope
+/// (field names, request id, timestamp and extra fields of `EnvelopeConfig`), errors included
+///
+/// # Example
+/// ```rust
+/// use nano_rs_core::config::envelope::EnvelopeConfig;
+/// use nano_rs_extra::axum::generator::gen_doc::apply_envelope;
+/// use nano_rs_extra::axum::rest::Envelope;
+/// use utoipa::openapi::OpenApiBuilder;
+///
+/// let mut openapi = OpenApiBuilder::new().build();
+/// let envelope_config = EnvelopeConfig {
+///     code_field: Some("errcode".to_string()),
+///     ..Default::default()
+/// };
+/// apply_envelope(&mut openapi, &Envelope::from(&envelope_config));
+/// ```
+pub fn apply_envelope(openapi: &mut OpenApi, envelope: &Envelope) {
+    apply_response_format(openapi, ResponseFormat::Envelope, envelope);
+}
+
+/// rewrite the json and text bodies (`body = String` is documented as text/plain, `RestResp`
+/// always answers json) of the generated openapi for a response format:
+/// - `Envelope`: every body in the envelope
+/// - `Problem`: success bodies in the envelope, errors as `application/problem+json`
+/// - `ProblemUnwrapped`: success bodies bare, errors as `application/problem+json`
+pub fn apply_response_format(openapi: &mut OpenApi, format: ResponseFormat, envelope: &Envelope) {
+    for path_item in openapi.paths.paths.values_mut() {
+        let operations = [
+            &mut path_item.get,
+            &mut path_item.put,
+            &mut path_item.post,
+            &mut path_item.delete,
+            &mut path_item.options,
+            &mut path_item.head,
+            &mut path_item.patch,
+            &mut path_item.trace,
+        ];
+        for operation in operations.into_iter().flatten() {
+            for (status, response) in operation.responses.responses.iter_mut() {
+                let RefOr::T(response) = response else {
+                    continue;
+                };
+                let success = !status.starts_with('4') && !status.starts_with('5');
+                let Some(content_type) = response
+                    .content
+                    .keys()
+                    .find(|content_type| is_rest_body(content_type))
+                    .cloned()
+                else {
+                    continue;
+                };
+                let mut content = response.content.shift_remove(&content_type).unwrap_or_default();
+                let content_type = if success || format == ResponseFormat::Envelope {
+                    if success && format == ResponseFormat::ProblemUnwrapped {
+                        // bare data, `RestResp<String>` is a json string
+                    } else {
+                        let data = content.schema.take();
+                        content.schema = Some(envelope_schema(envelope, data, success).into());
+                    }
+                    mime::APPLICATION_JSON.as_ref()
+                } else {
+                    content.schema = Some(problem_schema().into());
+                    APPLICATION_PROBLEM_JSON
+                };
+                response.content.insert(content_type.to_string(), content);
+            }
+        }
+    }
+}
+
+fn is_rest_body(content_type: &str) -> bool {
+    content_type.starts_with(mime::APPLICATION_JSON.as_ref())
+        || content_type.starts_with(mime::TEXT_PLAIN.as_ref())
+}
+
+fn envelope_schema(envelope: &Envelope, data: Option<RefOr<Schema>>, success: bool) -> Schema {
+    let mut code = ObjectBuilder::new()
+        .schema_type(Type::Integer)
+        .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int32)));
+    let mut msg = ObjectBuilder::new().schema_type(Type::String);
+    if success {
+        code = code.examples([envelope.success_code]);
+        msg = msg.examples([envelope.success_msg.as_str()]);
+    }
+    let mut object = ObjectBuilder::new()
+        .property(envelope.code_field.as_str(), code)
+        .required(envelope.code_field.as_str())
+        .property(envelope.msg_field.as_str(), msg)
+        .required(envelope.msg_field.as_str());
+    if let Some(data) = data {
+        object = object.property(envelope.data_field.as_str(), data);
+    }
+    if let Some(field) = &envelope.request_id_field {
+        object = object
+            .property(field.as_str(), ObjectBuilder::new().schema_type(Type::String))
+            .required(field.as_str());
+    }
+    if let Some(field) = &envelope.timestamp_field {
+        object = object
+            .property(
+                field.as_str(),
+                ObjectBuilder::new()
+                    .schema_type(Type::Integer)
+                    .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int64)))
+                    .description(Some("unix timestamp (millisecond)")),
+            )
+            .required(field.as_str());
+    }
+    for (key, value) in envelope.extra.iter() {
+        object = object
+            .property(
+                key.as_str(),
+                ObjectBuilder::new().schema_type(Type::String).examples([value.clone()]),
+            )
+            .required(key.as_str());
+    }
+    Schema::Object(object.build())
+}
+
+/// rfc 7807 problem document, with the business `code` extension
+fn problem_schema() -> Schema {
+    let string = || ObjectBuilder::new().schema_type(Type::String);
+    let integer = || {
+        ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int32)))
+    };
+    Schema::Object(
+        ObjectBuilder::new()
+            .property("type", string().examples(["about:blank"]))
+            .required("type")
+            .property("title", string())
+            .required("title")
+            .property("status", integer())
+            .required("status")
+            .property("detail", string())
+            .property("instance", string())
+            .property("code", integer())
+            .build(),
+    )
+}
+
 pub struct RsFile {
     pub path: PathBuf,
     pub mods: Vec<ItemMod>,
@@ -368,7 +610,7 @@ impl AxumGenDoc {
 
     fn is_utoipa_marco(
         &self,
-        api_fn: &ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
+        api_fn: &SynApiFn,
     ) -> bool {
         if let Some(attrs) = &api_fn.attrs {
             for attr in attrs {
@@ -447,3 +689,104 @@ impl Default for AxumGenDocBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use utoipa::openapi::path::{HttpMethod, OperationBuilder, PathItem};
+    use utoipa::openapi::{ContentBuilder, OpenApiBuilder, PathsBuilder, ResponseBuilder};
+
+    fn openapi() -> OpenApi {
+        let content = |schema: RefOr<Schema>| ContentBuilder::new().schema(Some(schema)).build();
+        let string = || {
+            RefOr::T(Schema::Object(
+                ObjectBuilder::new().schema_type(Type::String).build(),
+            ))
+        };
+        let operation = OperationBuilder::new()
+            .response(
+                "200",
+                ResponseBuilder::new().content("text/plain", content(string())),
+            )
+            .response(
+                "422",
+                ResponseBuilder::new().content(
+                    "application/json",
+                    content(RefOr::Ref(utoipa::openapi::Ref::from_schema_name(
+                        "ValidationErrors",
+                    ))),
+                ),
+            )
+            .response(
+                "201",
+                ResponseBuilder::new().content("application/octet-stream", content(string())),
+            )
+            .build();
+        OpenApiBuilder::new()
+            .paths(PathsBuilder::new().path("/pet", PathItem::new(HttpMethod::Get, operation)))
+            .build()
+    }
+
+    fn responses(openapi: &OpenApi) -> Value {
+        let operation = openapi.paths.paths["/pet"].get.as_ref().unwrap();
+        serde_json::to_value(&operation.responses).unwrap()
+    }
+
+    #[test]
+    fn envelope_every_body() {
+        let mut openapi = openapi();
+        let envelope = Envelope {
+            code_field: "errcode".to_string(),
+            ..Default::default()
+        };
+        apply_envelope(&mut openapi, &envelope);
+        let responses = responses(&openapi);
+        let ok = &responses["200"]["content"];
+        assert!(ok.get("text/plain").is_none());
+        let schema = &ok["application/json"]["schema"];
+        assert_eq!(schema["properties"]["errcode"]["examples"], json!([200]));
+        assert_eq!(schema["properties"]["data"]["type"], "string");
+        let invalid = &responses["422"]["content"]["application/json"]["schema"];
+        assert_eq!(
+            invalid["properties"]["data"]["$ref"],
+            "#/components/schemas/ValidationErrors"
+        );
+        assert!(invalid["properties"]["errcode"].get("examples").is_none());
+        let file = &responses["201"]["content"]["application/octet-stream"]["schema"];
+        assert_eq!(file["type"], "string");
+    }
+
+    #[test]
+    fn problem_errors() {
+        let mut openapi = openapi();
+        apply_response_format(&mut openapi, ResponseFormat::Problem, &Envelope::default());
+        let responses = responses(&openapi);
+        let ok = &responses["200"]["content"]["application/json"]["schema"];
+        assert_eq!(ok["properties"]["data"]["type"], "string");
+        let invalid = &responses["422"]["content"];
+        assert!(invalid.get("application/json").is_none());
+        assert_eq!(
+            invalid[APPLICATION_PROBLEM_JSON]["schema"]["required"],
+            json!(["type", "title", "status"])
+        );
+    }
+
+    #[test]
+    fn problem_unwrapped_success() {
+        let mut openapi = openapi();
+        apply_response_format(
+            &mut openapi,
+            ResponseFormat::ProblemUnwrapped,
+            &Envelope::default(),
+        );
+        let responses = responses(&openapi);
+        assert_eq!(
+            responses["200"]["content"]["application/json"]["schema"]["type"],
+            "string"
+        );
+        assert!(responses["422"]["content"]
+            .get(APPLICATION_PROBLEM_JSON)
+            .is_some());
+    }
+}
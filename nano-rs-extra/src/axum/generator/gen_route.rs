@@ -1,17 +1,16 @@
 #[cfg(feature = "utoipa_axum")]
 use crate::axum::generator::parse_utoipa_info;
 use crate::axum::generator::AxumGen;
-use nano_rs_build::api_fn::ApiFn;
+use crate::axum::middleware::rate_limit::Rate;
+use nano_rs_build::api_fn::{ApiFns, SynApiFn};
 use nano_rs_build::api_gen::GenRoute;
 use quote::__private::{Span, TokenStream};
 use quote::quote;
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
 use syn::{
-    parse_quote, parse_str, Attribute, Expr, ExprPath, FnArg, GenericArgument, Ident, ItemUse,
+    parse_quote, parse_str, Expr, ExprPath, FnArg, GenericArgument, Ident, ItemUse,
     PathArguments, PathSegment, Type, TypePath,
 };
 
@@ -28,10 +27,7 @@ impl GenRoute for AxumGenRoute {
         &self,
         _rs_files: Vec<PathBuf>,
         path_buf: PathBuf,
-        api_fns: HashMap<
-            String,
-            ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
-        >,
+        api_fns: ApiFns,
     ) {
         eprintln!("AxumGenRoute gen_route in {:?}", path_buf);
         let routes = path_buf.join(self.get_routes_file_path());
@@ -295,10 +291,7 @@ impl AxumGenRoute {
 
     fn parse_routes(
         &self,
-        mut api_fns: HashMap<
-            String,
-            ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
-        >,
+        mut api_fns: ApiFns,
         fn_route_code: &mut HashMap<String, Vec<TokenStream>>,
         use_crate_map: &mut HashMap<String, bool>,
     ) {
@@ -371,7 +364,7 @@ impl AxumGenRoute {
         fn_route_code: &mut HashMap<String, Vec<TokenStream>>,
         use_crate_map: &mut HashMap<String, bool>,
         name: &String,
-        api_fn: &ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
+        api_fn: &SynApiFn,
         path: &String,
         key: String,
     ) {
@@ -380,45 +373,91 @@ impl AxumGenRoute {
         ];
         if METHODS.contains(&api_fn.method.as_str()) {
             eprintln!("method --->{}", api_fn.method.clone());
+            let method_router = self.method_router(name, path, api_fn);
             self.method_insert(
                 api_fn.method.clone(),
                 fn_route_code,
                 use_crate_map,
-                name.clone(),
                 path.clone(),
+                method_router,
                 key,
             );
         }
     }
 
+    /// `get(handler)`, with an auth layer unless `open = true`
+    /// and a per-route rate limit layer when `rate_limit` is set
+    fn method_router(
+        &self,
+        name: &str,
+        path: &str,
+        api_fn: &SynApiFn,
+    ) -> TokenStream {
+        let method = api_fn.method.as_str();
+        let ident_fn_name: ExprPath = parse_str(name).expect("Failed to parse path");
+        let fn_name = Ident::new(method, Span::call_site());
+        let mut method_router = quote!(#fn_name(#ident_fn_name));
+        let roles = api_fn.roles.clone().unwrap_or_default();
+        if api_fn.permission.is_some() || !roles.is_empty() {
+            let permission = match api_fn.permission.as_ref() {
+                Some(permission) => quote!(Some(#permission)),
+                None => quote!(None),
+            };
+            method_router = quote!(
+                #method_router.route_layer(axum::middleware::from_fn_with_state(
+                    nano_rs::axum::middleware::permission::Requirement::new(#permission, &[#(#roles),*]),
+                    nano_rs::axum::middleware::permission::require_permission,
+                ))
+            );
+        }
+        if !api_fn.public {
+            method_router = quote!(
+                #method_router.route_layer(axum::middleware::from_fn(
+                    nano_rs::axum::middleware::auth::require_auth,
+                ))
+            );
+        }
+        if let Some(rate_limit) = api_fn.rate_limit.as_ref() {
+            if let Err(err) = rate_limit.parse::<Rate>() {
+                panic!("invalid rate_limit of {}: {}", name, err);
+            }
+            let scope = format!("{} {}", method.to_uppercase(), path);
+            method_router = quote!(
+                #method_router.route_layer(axum::middleware::from_fn_with_state(
+                    nano_rs::axum::middleware::rate_limit::RateLimiter::per_route(#rate_limit, #scope),
+                    nano_rs::axum::middleware::rate_limit::rate_limit,
+                ))
+            );
+        }
+        method_router
+    }
+
     fn method_insert(
         &self,
         method: String,
         fn_with_state: &mut HashMap<String, Vec<TokenStream>>,
         use_crate: &mut HashMap<String, bool>,
-        name: String,
         path: String,
+        method_router: TokenStream,
         key: String,
     ) {
         use_crate.insert(format!("use axum::routing::{};", method), true);
-        let ident_fn_name: ExprPath = parse_str(name.as_str()).expect("Failed to parse path");
-        let fn_name = Ident::new(method.as_str(), Span::call_site());
         if let Some(v) = fn_with_state.get_mut(&key) {
             v.push(quote!(
-                .route(#path,#fn_name(#ident_fn_name))
+                .route(#path,#method_router)
             ));
         } else {
             fn_with_state.insert(
                 key,
                 vec![quote!(
-                    .route(#path,#fn_name(#ident_fn_name))
+                    .route(#path,#method_router)
                 )],
             );
         }
     }
     fn get_fn_code_key(
         &self,
-        api_fn: &ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
+        api_fn: &SynApiFn,
         use_string: Option<String>,
     ) -> String {
         let mut key;
@@ -445,3 +484,31 @@ impl AxumGenRoute {
 }
 
 impl AxumGen for AxumGenRoute {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_fn(public: bool) -> SynApiFn {
+        SynApiFn {
+            api_fn_name: "get_pet".to_string(),
+            path: "/store/pet".to_string(),
+            method: "get".to_string(),
+            public,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn open_route_skips_auth() {
+        let gen = AxumGenRoute::new();
+        let open = gen
+            .method_router("crate::api::get_pet", "/store/pet", &api_fn(true))
+            .to_string();
+        assert!(!open.contains("require_auth"));
+        let closed = gen
+            .method_router("crate::api::get_pet", "/store/pet", &api_fn(false))
+            .to_string();
+        assert!(closed.contains("require_auth"));
+    }
+}
@@ -1,15 +1,10 @@
 #[cfg(feature = "utoipa_axum")]
-use nano_rs_build::api_fn::ApiFn;
+use nano_rs_build::api_fn::SynApiFn;
 use proc_macro2::Ident;
-#[cfg(feature = "utoipa_axum")]
-use syn::punctuated::Punctuated;
-#[cfg(feature = "utoipa_axum")]
-use syn::token::Comma;
-#[cfg(feature = "utoipa_axum")]
-use syn::{Attribute, FnArg};
 use syn::{ItemUse, UseGroup, UseName, UsePath, UseRename, UseTree};
 
 pub mod gen_api_info;
+pub mod gen_client;
 pub mod gen_doc;
 pub mod gen_route;
 
@@ -94,7 +89,7 @@ pub fn trans_utoipa_to_axum(old: String) -> String {
 
 #[cfg(feature = "utoipa_axum")]
 pub fn parse_utoipa_info(
-    api_fn: &mut ApiFn<String, Punctuated<FnArg, Comma>, Vec<ItemUse>, Vec<Attribute>>,
+    api_fn: &mut SynApiFn,
 ) {
     if let Some(attrs) = api_fn.clone().attrs {
         for attr in attrs.iter() {
@@ -1,5 +1,48 @@
-use axum::response::{Html, IntoResponse};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
 
-pub async fn handler_404() -> impl IntoResponse {
-    Html("<h5>404 page</h5>")
-}
\ No newline at end of file
+use crate::axum::rest::RestResp;
+
+/// default fallback, `RestResp` json with 404 status (html when the client asks for it)
+pub async fn handler_404(headers: HeaderMap) -> Response {
+    if accept_html(&headers) {
+        return (StatusCode::NOT_FOUND, Html("<h5>404 page</h5>")).into_response();
+    }
+    RestResp::<()> {
+        code: 404,
+        msg: "NOT_FOUND".to_string(),
+        data: None,
+        status: Some(StatusCode::NOT_FOUND),
+    }
+    .into_response()
+}
+
+/// default method not allowed fallback, `RestResp` json with 405 status (html when the client asks for it)
+pub async fn handler_405(headers: HeaderMap) -> Response {
+    if accept_html(&headers) {
+        return (StatusCode::METHOD_NOT_ALLOWED, Html("<h5>405 method not allowed</h5>"))
+            .into_response();
+    }
+    RestResp::<()> {
+        code: 405,
+        msg: "METHOD_NOT_ALLOWED".to_string(),
+        data: None,
+        status: Some(StatusCode::METHOD_NOT_ALLOWED),
+    }
+    .into_response()
+}
+
+/// client prefers html over json, e.g. a browser navigation
+pub fn accept_html(headers: &HeaderMap) -> bool {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+    else {
+        return false;
+    };
+    match (accept.find(mime::TEXT_HTML.as_ref()), accept.find(mime::APPLICATION_JSON.as_ref())) {
+        (Some(html), Some(json)) => html < json,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
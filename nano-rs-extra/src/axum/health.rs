@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::axum::rest::{envelope, RestResp};
+
+/// liveness route path
+pub const HEALTHZ_PATH: &str = "/healthz";
+
+/// readiness route path
+pub const READYZ_PATH: &str = "/readyz";
+
+/// boxed future returned by [`HealthCheck::check`]
+pub type CheckFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>;
+
+/// 就绪检查项
+/// A single readiness check (database ping, redis ping, grpc channel state etc..)
+pub trait HealthCheck: Send + Sync {
+    fn check(&self) -> CheckFuture<'_>;
+}
+
+impl HealthCheck for DatabaseConnection {
+    fn check(&self) -> CheckFuture<'_> {
+        Box::pin(async move {
+            self.ping().await?;
+            Ok(())
+        })
+    }
+}
+
+/// health check backed by an async closure
+pub struct FnHealthCheck<F>(pub F);
+
+impl<F, Fut> HealthCheck for FnHealthCheck<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    fn check(&self) -> CheckFuture<'_> {
+        Box::pin((self.0)())
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+/// result of a single check
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CheckResult {
+    pub status: HealthStatus,
+    /// check duration (millisecond)
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// aggregated readiness report, returned as `RestResp.data`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: BTreeMap<String, CheckResult>,
+}
+
+/// 就绪检查注册表
+/// Registry of readiness checks served under `/readyz`
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use nano_rs_extra::axum::health::HealthChecks;
+///
+/// let checks = HealthChecks::new()
+///     .timeout(Duration::from_secs(2))
+///     .add_check_fn("redis", || async {
+///         // redis PING things...
+///         Ok(())
+///     });
+/// ```
+#[derive(Clone)]
+pub struct HealthChecks {
+    checks: Vec<(String, Arc<dyn HealthCheck>)>,
+    timeout: Duration,
+}
+
+impl Default for HealthChecks {
+    fn default() -> Self {
+        HealthChecks {
+            checks: vec![],
+            timeout: Duration::from_secs(3),
+        }
+    }
+}
+
+impl HealthChecks {
+    pub fn new() -> Self {
+        HealthChecks::default()
+    }
+
+    /// set timeout of every single check, default 3s
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// register a check, e.g. a sea-orm `DatabaseConnection`
+    pub fn add_check<C>(mut self, name: impl Into<String>, check: C) -> Self
+    where
+        C: HealthCheck + 'static,
+    {
+        self.checks.push((name.into(), Arc::new(check)));
+        self
+    }
+
+    /// register an async closure as check
+    pub fn add_check_fn<F, Fut>(self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        self.add_check(name, FnHealthCheck(f))
+    }
+
+    /// run all checks concurrently
+    pub async fn run(&self) -> HealthReport {
+        let mut set = tokio::task::JoinSet::new();
+        for (name, check) in self.checks.iter() {
+            let (name, check, timeout) = (name.clone(), check.clone(), self.timeout);
+            set.spawn(async move {
+                let start = Instant::now();
+                let error = match tokio::time::timeout(timeout, check.check()).await {
+                    Ok(Ok(())) => None,
+                    Ok(Err(err)) => Some(err.to_string()),
+                    Err(_) => Some(format!("check timed out after {:?}", timeout)),
+                };
+                let result = CheckResult {
+                    status: if error.is_none() {
+                        HealthStatus::Up
+                    } else {
+                        HealthStatus::Down
+                    },
+                    duration_ms: start.elapsed().as_millis() as u64,
+                    error,
+                };
+                (name, result)
+            });
+        }
+        let mut checks = BTreeMap::new();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((name, result)) => {
+                    if let Some(error) = &result.error {
+                        tracing::warn!("health check {} failed: {}", name, error);
+                    }
+                    checks.insert(name, result);
+                }
+                Err(err) => tracing::error!("health check task failed: {}", err),
+            }
+        }
+        let status = if checks.len() == self.checks.len()
+            && checks.values().all(|c| c.status == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+        HealthReport { status, checks }
+    }
+
+    /// `/healthz` and `/readyz` routes
+    pub fn into_router(self) -> Router {
+        Router::new()
+            .route(HEALTHZ_PATH, get(liveness))
+            .route(READYZ_PATH, get(readiness))
+            .with_state(self)
+    }
+}
+
+/// liveness, always up while the process can serve requests
+pub async fn liveness() -> RestResp<HealthStatus> {
+    let envelope = envelope();
+    RestResp {
+        code: envelope.success_code,
+        msg: envelope.success_msg.clone(),
+        data: Some(HealthStatus::Up),
+        status: None,
+    }
+}
+
+/// readiness, 503 when any registered check is down
+pub async fn readiness(State(checks): State<HealthChecks>) -> Response {
+    let report = checks.run().await;
+    match report.status {
+        HealthStatus::Up => RestResp {
+            code: envelope().success_code,
+            msg: envelope().success_msg.clone(),
+            data: Some(report),
+            status: None,
+        }
+        .into_response(),
+        HealthStatus::Down => RestResp {
+            code: 503,
+            msg: "SERVICE_UNAVAILABLE".to_string(),
+            data: Some(report),
+            status: Some(StatusCode::SERVICE_UNAVAILABLE),
+        }
+        .into_response(),
+    }
+}
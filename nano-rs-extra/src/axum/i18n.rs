@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::sync::{Arc, RwLock};
+
+use axum::extract::Request;
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+use nano_rs_core::config::i18n::I18nConfig;
+use serde_yaml::Value;
+
+/// 多语言消息目录
+/// Message catalogs of every locale, messages use `{name}` placeholders
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::i18n::{set_i18n, I18n, Msg};
+///
+/// set_i18n(
+///     I18n::new("en")
+///         .add_message("en", "pet.not_found", "pet {id} not found")
+///         .add_message("zh-CN", "pet.not_found", "宠物 {id} 不存在"),
+/// );
+/// // outside a request the default locale is used
+/// assert_eq!(Msg::new("pet.not_found").arg("id", 7).to_string(), "pet 7 not found");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct I18n {
+    pub default_locale: String,
+    /// locale => message key => message
+    pub catalogs: HashMap<String, HashMap<String, String>>,
+}
+
+impl I18n {
+    pub fn new(default_locale: &str) -> Self {
+        I18n {
+            default_locale: default_locale.to_string(),
+            catalogs: HashMap::new(),
+        }
+    }
+
+    /// load the yaml catalogs of i18n config, nested keys are joined with `.`
+    pub fn from_config(i18n_config: &I18nConfig) -> Result<Self, String> {
+        let mut i18n = I18n::new(i18n_config.get_default_locale().as_str());
+        for (locale, path) in i18n_config.catalogs.iter() {
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("read i18n catalog {} failed: {}", path, e))?;
+            let value: Value = serde_yaml::from_str(content.as_str())
+                .map_err(|e| format!("parse i18n catalog {} failed: {}", path, e))?;
+            let mut messages = HashMap::new();
+            flatten(None, &value, &mut messages);
+            i18n = i18n.add_catalog(locale, messages);
+        }
+        Ok(i18n)
+    }
+
+    pub fn add_catalog(mut self, locale: &str, messages: HashMap<String, String>) -> Self {
+        self.catalogs
+            .entry(locale.to_string())
+            .or_default()
+            .extend(messages);
+        self
+    }
+
+    pub fn add_message(mut self, locale: &str, key: &str, message: &str) -> Self {
+        self.catalogs
+            .entry(locale.to_string())
+            .or_default()
+            .insert(key.to_string(), message.to_string());
+        self
+    }
+
+    /// best catalog for an `Accept-Language` header, by q value, then exact tag, then language
+    pub fn negotiate(&self, accept_language: &str) -> Option<String> {
+        let mut tags: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .filter_map(|part| {
+                let mut params = part.split(';');
+                let tag = params.next()?.trim();
+                let q = params
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse().ok())
+                    .unwrap_or(1.0);
+                (!tag.is_empty() && tag != "*" && q > 0.0).then_some((tag, q))
+            })
+            .collect();
+        tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+        tags.iter().find_map(|(tag, _)| self.match_locale(tag))
+    }
+
+    fn match_locale(&self, tag: &str) -> Option<String> {
+        let language = |locale: &str| {
+            locale
+                .split(['-', '_'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        };
+        self.catalogs
+            .keys()
+            .find(|locale| locale.eq_ignore_ascii_case(tag))
+            .or_else(|| {
+                self.catalogs
+                    .keys()
+                    .find(|locale| language(locale) == language(tag))
+            })
+            .cloned()
+    }
+
+    /// message of `key` in `locale`, else in the default locale, with args filled in
+    ///
+    /// placeholders are filled in one pass, `{name}` inside an arg value is kept as is
+    ///
+    /// # Example
+    /// ```rust
+    /// use nano_rs_extra::axum::i18n::I18n;
+    ///
+    /// let i18n = I18n::new("en").add_message("en", "pet.name_taken", "{0} is taken by {1}");
+    /// let args = [("0".to_string(), "{1}".to_string()), ("1".to_string(), "mantou".to_string())];
+    /// assert_eq!(i18n.lookup(None, "pet.name_taken", &args).as_deref(), Some("{1} is taken by mantou"));
+    /// ```
+    pub fn lookup(
+        &self,
+        locale: Option<&str>,
+        key: &str,
+        args: &[(String, String)],
+    ) -> Option<String> {
+        let message = locale
+            .and_then(|locale| self.catalogs.get(locale))
+            .and_then(|messages| messages.get(key))
+            .or_else(|| {
+                self.catalogs
+                    .get(self.default_locale.as_str())
+                    .and_then(|messages| messages.get(key))
+            })?;
+        Some(fill(message, args))
+    }
+}
+
+/// replace the `{name}` placeholders of `template` by the args
+fn fill(template: &str, args: &[(String, String)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let name = rest[1..]
+            .find(['{', '}'])
+            .filter(|end| rest.as_bytes()[end + 1] == b'}')
+            .map(|end| &rest[1..end + 1]);
+        match name.and_then(|name| args.iter().find(|(arg, _)| arg == name)) {
+            Some((name, value)) => {
+                message.push_str(value);
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+fn flatten(prefix: Option<String>, value: &Value, messages: &mut HashMap<String, String>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter() {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+                let key = match &prefix {
+                    Some(prefix) => format!("{}.{}", prefix, key),
+                    None => key.to_string(),
+                };
+                flatten(Some(key), value, messages);
+            }
+        }
+        Value::String(message) => {
+            if let Some(key) = prefix {
+                messages.insert(key, message.clone());
+            }
+        }
+        Value::Number(_) | Value::Bool(_) => {
+            if let (Some(key), Ok(message)) = (prefix, serde_yaml::to_string(value)) {
+                messages.insert(key, message.trim().to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+static I18N: RwLock<Option<Arc<I18n>>> = RwLock::new(None);
+
+/// set app wide catalogs, `AppStarter` sets them from `RestConfig.i18n`
+pub fn set_i18n(i18n: I18n) {
+    *I18N.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(i18n));
+}
+
+pub fn i18n() -> Option<Arc<I18n>> {
+    I18N.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+tokio::task_local! {
+    static LOCALE: Option<String>;
+}
+
+/// locale of the current request, negotiated by `with_locale`
+pub fn locale() -> Option<String> {
+    LOCALE.try_with(|locale| locale.clone()).ok().flatten()
+}
+
+/// message of `key` in the request locale, `None` when no catalog has it
+pub fn lookup(key: &str, args: &[(String, String)]) -> Option<String> {
+    i18n()?.lookup(locale().as_deref(), key, args)
+}
+
+/// pick the request locale from `Accept-Language`, used by `Msg` and error messages
+pub async fn with_locale(req: Request, next: Next) -> Response {
+    let locale = i18n().and_then(|i18n| {
+        req.headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| i18n.negotiate(value))
+    });
+    LOCALE.scope(locale, next.run(req)).await
+}
+
+/// 多语言消息
+/// Message key and args, resolved in the request locale when displayed
+///
+/// pass it to `biz_err`, the key itself is the message when no catalog has it
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::errors::ServerError;
+/// use nano_rs_extra::axum::i18n::Msg;
+/// use nano_rs_extra::axum::rest::{biz_err, RestResp};
+///
+/// pub async fn get_pet(id: i64) -> Result<RestResp<()>, ServerError> {
+///     biz_err(404, Msg::new("pet.not_found").arg("id", id))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Msg {
+    pub key: String,
+    pub args: Vec<(String, String)>,
+}
+
+impl Msg {
+    pub fn new(key: impl Into<String>) -> Self {
+        Msg {
+            key: key.into(),
+            args: vec![],
+        }
+    }
+
+    pub fn arg(mut self, name: impl Into<String>, value: impl Display) -> Self {
+        self.args.push((name.into(), value.to_string()));
+        self
+    }
+}
+
+impl Display for Msg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match lookup(self.key.as_str(), &self.args) {
+            Some(message) => f.write_str(message.as_str()),
+            None => f.write_str(self.key.as_str()),
+        }
+    }
+}
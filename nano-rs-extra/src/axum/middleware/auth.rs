@@ -0,0 +1,594 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::Request;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use nano_rs_core::config::auth::AuthConfig;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::{Pkcs1v15Sign, RsaPublicKey};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::axum::i18n;
+use crate::axum::rest::RestResp;
+use crate::axum::session::Session;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JwtAlgorithm {
+    HS256,
+    HS384,
+    HS512,
+    RS256,
+    RS384,
+    RS512,
+}
+
+impl JwtAlgorithm {
+    pub fn is_hmac(&self) -> bool {
+        matches!(self, JwtAlgorithm::HS256 | JwtAlgorithm::HS384 | JwtAlgorithm::HS512)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JwtAlgorithm::HS256 => "HS256",
+            JwtAlgorithm::HS384 => "HS384",
+            JwtAlgorithm::HS512 => "HS512",
+            JwtAlgorithm::RS256 => "RS256",
+            JwtAlgorithm::RS384 => "RS384",
+            JwtAlgorithm::RS512 => "RS512",
+        }
+    }
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::HS256),
+            "HS384" => Ok(JwtAlgorithm::HS384),
+            "HS512" => Ok(JwtAlgorithm::HS512),
+            "RS256" => Ok(JwtAlgorithm::RS256),
+            "RS384" => Ok(JwtAlgorithm::RS384),
+            "RS512" => Ok(JwtAlgorithm::RS512),
+            _ => Err(format!("unsupported jwt algorithm: {}", s)),
+        }
+    }
+}
+
+/// why a token was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthError {
+    MissingToken,
+    /// no bearer token and no logged in session
+    NotLoggedIn,
+    Malformed,
+    InvalidAlgorithm,
+    InvalidSignature,
+    Expired,
+    NotYetValid,
+    InvalidIssuer,
+    InvalidAudience,
+    /// signing with a public key only verifier etc..
+    Unsupported(String),
+    /// a protected route without `auth` or `session` config
+    NotConfigured,
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::MissingToken => write!(f, "missing bearer token"),
+            AuthError::NotLoggedIn => write!(f, "not logged in"),
+            AuthError::Malformed => write!(f, "malformed token"),
+            AuthError::InvalidAlgorithm => write!(f, "invalid token algorithm"),
+            AuthError::InvalidSignature => write!(f, "invalid token signature"),
+            AuthError::Expired => write!(f, "token expired"),
+            AuthError::NotYetValid => write!(f, "token not yet valid"),
+            AuthError::InvalidIssuer => write!(f, "invalid token issuer"),
+            AuthError::InvalidAudience => write!(f, "invalid token audience"),
+            AuthError::Unsupported(msg) => write!(f, "{}", msg),
+            AuthError::NotConfigured => write!(f, "authentication not configured"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// 401 `RestResp` with `WWW-Authenticate: Bearer`, msg localized by the `auth.unauthorized` key
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        tracing::debug!("unauthorized: {}", self);
+        let msg = i18n::lookup("auth.unauthorized", &[("detail".to_string(), self.to_string())])
+            .unwrap_or(self.to_string());
+        let mut res = RestResp::<()> {
+            code: 401,
+            msg,
+            data: None,
+            status: Some(StatusCode::UNAUTHORIZED),
+        }
+        .into_response();
+        res.headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        res
+    }
+}
+
+enum JwtKey {
+    Hmac(Vec<u8>),
+    Rsa(Box<RsaPublicKey>),
+}
+
+/// verified claims of the request, inserted into extensions by `require_auth`
+#[derive(Debug, Clone)]
+pub struct VerifiedClaims(pub Value);
+
+/// jwt 校验
+/// Verify jwt signature, `exp`, `nbf`, `iss` and `aud`
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::middleware::auth::{JwtAlgorithm, JwtVerifier};
+/// use serde_json::json;
+///
+/// let verifier = JwtVerifier::hmac(JwtAlgorithm::HS256, b"secret").issuer("nano");
+/// let token = verifier.sign(&json!({"sub": "1", "iss": "nano", "exp": 4102444800u64})).unwrap();
+/// assert_eq!(verifier.verify(token.as_str()).unwrap()["sub"], "1");
+/// ```
+pub struct JwtVerifier {
+    algorithm: JwtAlgorithm,
+    key: JwtKey,
+    issuer: Option<String>,
+    audience: Option<String>,
+    leeway: u64,
+}
+
+impl JwtVerifier {
+    /// verifier of HS256, HS384 or HS512 tokens
+    pub fn hmac(algorithm: JwtAlgorithm, secret: &[u8]) -> Self {
+        JwtVerifier {
+            algorithm,
+            key: JwtKey::Hmac(secret.to_vec()),
+            issuer: None,
+            audience: None,
+            leeway: 60,
+        }
+    }
+
+    /// verifier of RS256, RS384 or RS512 tokens, `pem` is a pkcs1 or pkcs8 public key
+    pub fn rsa(algorithm: JwtAlgorithm, pem: &str) -> Result<Self, String> {
+        let key = RsaPublicKey::from_public_key_pem(pem)
+            .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+            .map_err(|e| format!("invalid rsa public key: {}", e))?;
+        Ok(JwtVerifier {
+            algorithm,
+            key: JwtKey::Rsa(Box::new(key)),
+            issuer: None,
+            audience: None,
+            leeway: 60,
+        })
+    }
+
+    pub fn from_config(auth_config: &AuthConfig) -> Result<Self, String> {
+        let algorithm: JwtAlgorithm = auth_config.get_algorithm().parse()?;
+        let verifier = if algorithm.is_hmac() {
+            let secret = auth_config
+                .secret
+                .as_ref()
+                .ok_or(format!("auth secret is required by {}", algorithm.as_str()))?;
+            JwtVerifier::hmac(algorithm, secret.as_bytes())
+        } else {
+            let path = auth_config
+                .public_key_file
+                .as_ref()
+                .ok_or(format!("auth public_key_file is required by {}", algorithm.as_str()))?;
+            let pem = fs::read_to_string(path)
+                .map_err(|e| format!("read auth public key {} failed: {}", path, e))?;
+            JwtVerifier::rsa(algorithm, pem.as_str())?
+        };
+        let mut verifier = verifier.leeway(auth_config.get_leeway());
+        verifier.issuer = auth_config.issuer.clone();
+        verifier.audience = auth_config.audience.clone();
+        Ok(verifier)
+    }
+
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.issuer = Some(issuer.to_string());
+        self
+    }
+
+    pub fn audience(mut self, audience: &str) -> Self {
+        self.audience = Some(audience.to_string());
+        self
+    }
+
+    /// allowed clock skew (second)
+    pub fn leeway(mut self, leeway: u64) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// verify token and return its claims
+    pub fn verify(&self, token: &str) -> Result<Value, AuthError> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(AuthError::Malformed);
+        };
+        let decode = |part: &str| URL_SAFE_NO_PAD.decode(part).map_err(|_| AuthError::Malformed);
+        let jwt_header: Value = serde_json::from_slice(&decode(header)?).map_err(|_| AuthError::Malformed)?;
+        if jwt_header["alg"].as_str() != Some(self.algorithm.as_str()) {
+            return Err(AuthError::InvalidAlgorithm);
+        }
+        let message = &token[..header.len() + 1 + payload.len()];
+        self.verify_signature(message.as_bytes(), &decode(signature)?)?;
+        let claims: Value = serde_json::from_slice(&decode(payload)?).map_err(|_| AuthError::Malformed)?;
+        self.validate_claims(&claims)?;
+        Ok(claims)
+    }
+
+    /// sign claims with the hmac secret, e.g. in a login handler
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, AuthError> {
+        let JwtKey::Hmac(secret) = &self.key else {
+            return Err(AuthError::Unsupported("rsa verifier can not sign tokens".to_string()));
+        };
+        let header = json!({"alg": self.algorithm.as_str(), "typ": "JWT"});
+        let encode = |value: &Value| URL_SAFE_NO_PAD.encode(value.to_string());
+        let claims = serde_json::to_value(claims).map_err(|e| AuthError::Unsupported(e.to_string()))?;
+        let message = format!("{}.{}", encode(&header), encode(&claims));
+        let signature = hmac_sign(self.algorithm, secret, message.as_bytes());
+        Ok(format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature)))
+    }
+
+    fn verify_signature(&self, message: &[u8], signature: &[u8]) -> Result<(), AuthError> {
+        let valid = match &self.key {
+            JwtKey::Hmac(secret) => hmac_verify(self.algorithm, secret, message, signature),
+            JwtKey::Rsa(key) => {
+                let result = match self.algorithm {
+                    JwtAlgorithm::RS256 => key.verify(Pkcs1v15Sign::new::<Sha256>(), &Sha256::digest(message), signature),
+                    JwtAlgorithm::RS384 => key.verify(Pkcs1v15Sign::new::<Sha384>(), &Sha384::digest(message), signature),
+                    JwtAlgorithm::RS512 => key.verify(Pkcs1v15Sign::new::<Sha512>(), &Sha512::digest(message), signature),
+                    _ => return Err(AuthError::InvalidAlgorithm),
+                };
+                result.is_ok()
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(AuthError::InvalidSignature)
+        }
+    }
+
+    fn validate_claims(&self, claims: &Value) -> Result<(), AuthError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let exp = claims["exp"].as_u64().ok_or(AuthError::Malformed)?;
+        if now > exp.saturating_add(self.leeway) {
+            return Err(AuthError::Expired);
+        }
+        if let Some(nbf) = claims["nbf"].as_u64() {
+            if now.saturating_add(self.leeway) < nbf {
+                return Err(AuthError::NotYetValid);
+            }
+        }
+        if let Some(issuer) = &self.issuer {
+            if claims["iss"].as_str() != Some(issuer.as_str()) {
+                return Err(AuthError::InvalidIssuer);
+            }
+        }
+        if let Some(audience) = &self.audience {
+            let matched = match &claims["aud"] {
+                Value::String(aud) => aud == audience,
+                Value::Array(auds) => auds.iter().any(|aud| aud.as_str() == Some(audience.as_str())),
+                _ => false,
+            };
+            if !matched {
+                return Err(AuthError::InvalidAudience);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hmac_sign(algorithm: JwtAlgorithm, secret: &[u8], message: &[u8]) -> Vec<u8> {
+    macro_rules! sign {
+        ($digest:ty) => {{
+            let mut mac = Hmac::<$digest>::new_from_slice(secret).expect("hmac accepts any key size");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }};
+    }
+    match algorithm {
+        JwtAlgorithm::HS384 => sign!(Sha384),
+        JwtAlgorithm::HS512 => sign!(Sha512),
+        _ => sign!(Sha256),
+    }
+}
+
+fn hmac_verify(algorithm: JwtAlgorithm, secret: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    macro_rules! verify {
+        ($digest:ty) => {{
+            let mut mac = Hmac::<$digest>::new_from_slice(secret).expect("hmac accepts any key size");
+            mac.update(message);
+            mac.verify_slice(signature).is_ok()
+        }};
+    }
+    match algorithm {
+        JwtAlgorithm::HS256 => verify!(Sha256),
+        JwtAlgorithm::HS384 => verify!(Sha384),
+        JwtAlgorithm::HS512 => verify!(Sha512),
+        _ => false,
+    }
+}
+
+static VERIFIER: RwLock<Option<Arc<JwtVerifier>>> = RwLock::new(None);
+
+/// set verifier of `require_auth`, `AppStarter` sets it from `RestConfig.auth`
+pub fn set_jwt_verifier(verifier: JwtVerifier) {
+    *VERIFIER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(verifier));
+}
+
+pub fn jwt_verifier() -> Option<Arc<JwtVerifier>> {
+    VERIFIER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+static AUTH_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// let `require_auth` accept every request, `AppStarter` calls it for `auth.enable: false`
+pub fn disable_auth() {
+    AUTH_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// 鉴权中间件
+/// Verify the bearer token and insert `VerifiedClaims`, rejects with 401 `RestResp`
+///
+/// without a bearer token the claims of `Session::login` are accepted. generated routes
+/// without `open = true` get this layer, it fails closed (401) when neither a verifier nor the
+/// session layer is set, disable auth explicitly with `auth.enable: false`
+pub async fn require_auth(req: Request, next: Next) -> Response {
+    if AUTH_DISABLED.load(Ordering::Relaxed) {
+        return next.run(req).await;
+    }
+    authenticate(jwt_verifier(), req, next).await
+}
+
+async fn authenticate(verifier: Option<Arc<JwtVerifier>>, mut req: Request, next: Next) -> Response {
+    let session = req.extensions().get::<Session>().cloned();
+    if verifier.is_none() && session.is_none() {
+        tracing::error!(
+            "{} requires auth but neither `auth` nor `session` is configured",
+            req.uri().path()
+        );
+        return AuthError::NotConfigured.into_response();
+    }
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .strip_prefix("Bearer ")
+                .or_else(|| value.strip_prefix("bearer "))
+        })
+        .map(|token| token.trim());
+    let claims = match (token, verifier) {
+        (Some(token), Some(verifier)) => verifier.verify(token),
+        (_, verifier) => match session.and_then(|session| session.claims()) {
+            Some(claims) => Ok(claims),
+            None if verifier.is_some() => Err(AuthError::MissingToken),
+            None => Err(AuthError::NotLoggedIn),
+        },
+    };
+    match claims {
+        Ok(claims) => {
+            req.extensions_mut().insert(VerifiedClaims(claims));
+            next.run(req).await
+        }
+        Err(err) => err.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::Router;
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::axum::extractor::Claims;
+
+    const SECRET: &[u8] = b"nano-rs test secret";
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    fn verifier() -> JwtVerifier {
+        JwtVerifier::hmac(JwtAlgorithm::HS256, SECRET)
+    }
+
+    /// token of any header, signed with the HS256 test secret
+    fn token_with_header(header: Value, claims: Value) -> String {
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let signature = hmac_sign(JwtAlgorithm::HS256, SECRET, message.as_bytes());
+        format!("{}.{}", message, URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    #[test]
+    fn verify_signed_token() {
+        let token = verifier().sign(&json!({"sub": "1", "exp": now() + 60})).unwrap();
+        assert_eq!(verifier().verify(token.as_str()).unwrap()["sub"], "1");
+    }
+
+    #[test]
+    fn reject_algorithm_mismatch() {
+        let claims = json!({"sub": "1", "exp": now() + 60});
+        let none = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(json!({"alg": "none"}).to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        assert_eq!(verifier().verify(none.as_str()), Err(AuthError::InvalidAlgorithm));
+        let rs256 = token_with_header(json!({"alg": "RS256", "typ": "JWT"}), claims.clone());
+        assert_eq!(verifier().verify(rs256.as_str()), Err(AuthError::InvalidAlgorithm));
+        let hs384 = JwtVerifier::hmac(JwtAlgorithm::HS384, SECRET).sign(&claims).unwrap();
+        assert_eq!(verifier().verify(hs384.as_str()), Err(AuthError::InvalidAlgorithm));
+    }
+
+    #[test]
+    fn reject_bad_signature() {
+        let claims = json!({"sub": "1", "exp": now() + 60});
+        let other = JwtVerifier::hmac(JwtAlgorithm::HS256, b"other secret").sign(&claims).unwrap();
+        assert_eq!(verifier().verify(other.as_str()), Err(AuthError::InvalidSignature));
+        let token = verifier().sign(&claims).unwrap();
+        let (message, _) = token.rsplit_once('.').unwrap();
+        let forged = verifier().sign(&json!({"sub": "admin", "exp": now() + 60})).unwrap();
+        let (_, signature) = forged.rsplit_once('.').unwrap();
+        let tampered = format!("{}.{}", message, signature);
+        assert_eq!(verifier().verify(tampered.as_str()), Err(AuthError::InvalidSignature));
+        assert_eq!(verifier().verify("a.b"), Err(AuthError::Malformed));
+    }
+
+    #[test]
+    fn check_exp_and_nbf_with_leeway() {
+        let verify = |claims: Value| verifier().leeway(60).verify(verifier().sign(&claims).unwrap().as_str());
+        assert!(verify(json!({"exp": now() - 30})).is_ok());
+        assert_eq!(verify(json!({"exp": now() - 120})), Err(AuthError::Expired));
+        assert_eq!(verify(json!({"sub": "1"})), Err(AuthError::Malformed));
+        assert!(verify(json!({"exp": now() + 60, "nbf": now() + 30})).is_ok());
+        assert_eq!(
+            verify(json!({"exp": now() + 600, "nbf": now() + 120})),
+            Err(AuthError::NotYetValid)
+        );
+        // attacker controlled numbers must not overflow
+        assert!(verify(json!({"exp": u64::MAX})).is_ok());
+        assert_eq!(
+            verify(json!({"exp": u64::MAX, "nbf": u64::MAX})),
+            Err(AuthError::NotYetValid)
+        );
+        let verify_max_leeway =
+            |claims: Value| verifier().leeway(u64::MAX).verify(verifier().sign(&claims).unwrap().as_str());
+        assert!(verify_max_leeway(json!({"exp": u64::MAX, "nbf": u64::MAX})).is_ok());
+    }
+
+    #[test]
+    fn check_issuer_and_audience() {
+        let verifier = verifier().issuer("nano").audience("pet");
+        let verify = |claims: Value| verifier.verify(verifier.sign(&claims).unwrap().as_str());
+        let exp = now() + 60;
+        assert!(verify(json!({"exp": exp, "iss": "nano", "aud": "pet"})).is_ok());
+        assert!(verify(json!({"exp": exp, "iss": "nano", "aud": ["store", "pet"]})).is_ok());
+        assert_eq!(
+            verify(json!({"exp": exp, "iss": "other", "aud": "pet"})),
+            Err(AuthError::InvalidIssuer)
+        );
+        assert_eq!(verify(json!({"exp": exp, "aud": "pet"})), Err(AuthError::InvalidIssuer));
+        assert_eq!(
+            verify(json!({"exp": exp, "iss": "nano", "aud": "store"})),
+            Err(AuthError::InvalidAudience)
+        );
+        assert_eq!(
+            verify(json!({"exp": exp, "iss": "nano", "aud": ["store"]})),
+            Err(AuthError::InvalidAudience)
+        );
+        assert_eq!(verify(json!({"exp": exp, "iss": "nano"})), Err(AuthError::InvalidAudience));
+    }
+
+    #[derive(Deserialize)]
+    struct UserClaims {
+        sub: String,
+        roles: Vec<String>,
+    }
+
+    async fn whoami(Claims(claims): Claims<UserClaims>) -> String {
+        format!("{} {}", claims.sub, claims.roles.join(","))
+    }
+
+    /// `require_auth` with a verifier of its own, the global one is never set by tests
+    fn app(verifier: Option<JwtVerifier>) -> Router {
+        let verifier = verifier.map(Arc::new);
+        let auth = axum::middleware::from_fn(move |req: Request, next: Next| {
+            authenticate(verifier.clone(), req, next)
+        });
+        Router::new().route("/me", get(whoami).route_layer(auth))
+    }
+
+    async fn call(token: Option<&str>) -> Response {
+        call_app(app(Some(verifier())), token).await
+    }
+
+    async fn call_app(app: Router, token: Option<&str>) -> Response {
+        let mut req = Request::builder().uri("/me");
+        if let Some(token) = token {
+            req = req.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    async fn body(res: Response) -> String {
+        let bytes = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn missing_token_is_unauthorized() {
+        let res = call(None).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()[header::WWW_AUTHENTICATE], "Bearer");
+        assert!(body(res).await.contains("\"code\":401"));
+    }
+
+    #[tokio::test]
+    async fn unconfigured_auth_fails_closed() {
+        let token = verifier().sign(&json!({"sub": "mantou", "exp": now() + 60})).unwrap();
+        for token in [None, Some(token.as_str())] {
+            let res = call_app(app(None), token).await;
+            assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_token_is_unauthorized() {
+        let res = call(Some("not.a.token")).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    }
+
+    #[tokio::test]
+    async fn claims_are_decoded() {
+        let token = verifier()
+            .sign(&json!({"sub": "mantou", "roles": ["admin", "groomer"], "exp": now() + 60}))
+            .unwrap();
+        let res = call(Some(token.as_str())).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(body(res).await, "mantou admin,groomer");
+    }
+
+    #[tokio::test]
+    async fn undecodable_claims_are_rejected() {
+        let token = verifier().sign(&json!({"sub": "mantou", "exp": now() + 60})).unwrap();
+        let res = call(Some(token.as_str())).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,88 @@
+use axum::body::HttpBody;
+use axum::http::Response;
+use nano_rs_core::config::compression::CompressionConfig;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::decompression::RequestDecompressionLayer;
+
+const ALGORITHMS: [&str; 4] = ["gzip", "br", "zstd", "deflate"];
+
+const DEFAULT_MIN_SIZE: u16 = 1024;
+
+/// compress responses above `min_size` whose content type is not excluded
+#[derive(Clone)]
+pub struct CompressionPredicate {
+    min_size: SizeAbove,
+    excluded: Vec<NotForContentType>,
+}
+
+impl CompressionPredicate {
+    pub fn new(compression_config: &CompressionConfig) -> Self {
+        let mut excluded = vec![
+            NotForContentType::GRPC,
+            NotForContentType::IMAGES,
+            NotForContentType::SSE,
+        ];
+        if let Some(content_types) = &compression_config.exclude_content_types {
+            excluded.extend(
+                content_types
+                    .iter()
+                    .map(|content_type| NotForContentType::new(content_type.trim())),
+            );
+        }
+        CompressionPredicate {
+            min_size: SizeAbove::new(compression_config.min_size.unwrap_or(DEFAULT_MIN_SIZE)),
+            excluded,
+        }
+    }
+}
+
+impl Predicate for CompressionPredicate {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        self.min_size.should_compress(response)
+            && self
+                .excluded
+                .iter()
+                .all(|excluded| excluded.should_compress(response))
+    }
+}
+
+/// build response compression layer from compression config
+pub fn compression_layer(
+    compression_config: &CompressionConfig,
+) -> CompressionLayer<CompressionPredicate> {
+    let algorithms = enabled_algorithms(compression_config);
+    CompressionLayer::new()
+        .gzip(algorithms.contains(&"gzip"))
+        .br(algorithms.contains(&"br"))
+        .zstd(algorithms.contains(&"zstd"))
+        .deflate(algorithms.contains(&"deflate"))
+        .compress_when(CompressionPredicate::new(compression_config))
+}
+
+/// build request decompression layer from compression config,
+/// unsupported `Content-Encoding` is rejected with 415
+pub fn decompression_layer(compression_config: &CompressionConfig) -> RequestDecompressionLayer {
+    let algorithms = enabled_algorithms(compression_config);
+    RequestDecompressionLayer::new()
+        .gzip(algorithms.contains(&"gzip"))
+        .br(algorithms.contains(&"br"))
+        .zstd(algorithms.contains(&"zstd"))
+        .deflate(algorithms.contains(&"deflate"))
+}
+
+fn enabled_algorithms(compression_config: &CompressionConfig) -> Vec<&'static str> {
+    let configured = compression_config.get_algorithms();
+    for algorithm in configured.iter() {
+        if !ALGORITHMS.contains(&algorithm.as_str()) {
+            panic!("unknown compression algorithm: {}", algorithm);
+        }
+    }
+    ALGORITHMS
+        .into_iter()
+        .filter(|algorithm| configured.iter().any(|a| a == algorithm))
+        .collect()
+}
@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use nano_rs_core::config::cors::CorsConfig;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer, ExposeHeaders};
+
+const WILDCARD: &str = "*";
+
+const DEFAULT_METHODS: [Method; 7] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::DELETE,
+    Method::PATCH,
+    Method::OPTIONS,
+    Method::HEAD,
+];
+
+/// build cors layer from cors config
+///
+/// panics on an invalid config, see `CorsConfig::validate` ("*" with `allow_credentials`)
+pub fn cors_layer(cors_config: &CorsConfig) -> CorsLayer {
+    if let Err(err) = cors_config.validate() {
+        panic!("invalid cors config: {}", err);
+    }
+    let mut layer = CorsLayer::new()
+        .allow_origin(allow_origin(cors_config))
+        .allow_methods(allow_methods(cors_config))
+        .allow_credentials(cors_config.allow_credentials.unwrap_or(false));
+    if let Some(headers) = &cors_config.allow_headers {
+        layer = layer.allow_headers(if is_any(headers) {
+            AllowHeaders::from(Any)
+        } else {
+            AllowHeaders::list(header_names(headers))
+        });
+    }
+    if let Some(headers) = &cors_config.expose_headers {
+        layer = layer.expose_headers(if is_any(headers) {
+            ExposeHeaders::from(Any)
+        } else {
+            ExposeHeaders::list(header_names(headers))
+        });
+    }
+    if let Some(max_age) = cors_config.max_age {
+        layer = layer.max_age(Duration::from_secs(max_age));
+    }
+    layer
+}
+
+fn allow_origin(cors_config: &CorsConfig) -> AllowOrigin {
+    let origins = cors_config.allow_origins.clone().unwrap_or_default();
+    if is_any(&origins) {
+        return AllowOrigin::any();
+    }
+    if origins.iter().any(|origin| origin.contains(WILDCARD)) {
+        return AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin.to_str().is_ok_and(|origin| {
+                origins
+                    .iter()
+                    .any(|pattern| wildcard_match(pattern.as_str(), origin))
+            })
+        });
+    }
+    AllowOrigin::list(origins.iter().map(|origin| {
+        HeaderValue::from_str(origin)
+            .unwrap_or_else(|_| panic!("invalid cors allow origin: {}", origin))
+    }))
+}
+
+fn allow_methods(cors_config: &CorsConfig) -> AllowMethods {
+    match &cors_config.allow_methods {
+        None => AllowMethods::list(DEFAULT_METHODS),
+        Some(methods) if is_any(methods) => AllowMethods::any(),
+        Some(methods) => AllowMethods::list(methods.iter().map(|method| {
+            Method::from_bytes(method.to_uppercase().as_bytes())
+                .unwrap_or_else(|_| panic!("invalid cors allow method: {}", method))
+        })),
+    }
+}
+
+fn header_names(headers: &[String]) -> Vec<HeaderName> {
+    headers
+        .iter()
+        .map(|header| {
+            HeaderName::from_bytes(header.as_bytes())
+                .unwrap_or_else(|_| panic!("invalid cors header: {}", header))
+        })
+        .collect()
+}
+
+fn is_any(values: &[String]) -> bool {
+    values.iter().any(|value| value == WILDCARD)
+}
+
+/// match origin against pattern, `*` matches any sequence of characters
+fn wildcard_match(pattern: &str, origin: &str) -> bool {
+    let parts: Vec<&str> = pattern.split(WILDCARD).collect();
+    if parts.len() == 1 {
+        return pattern == origin;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !origin.starts_with(first)
+        || !origin.ends_with(last)
+        || origin.len() < first.len() + last.len()
+    {
+        return false;
+    }
+    let mut rest = &origin[first.len()..origin.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_ORIGIN, ORIGIN,
+    };
+    use axum::http::Request;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn config(origins: &[&str], credentials: bool) -> CorsConfig {
+        CorsConfig {
+            allow_origins: Some(origins.iter().map(|origin| origin.to_string()).collect()),
+            allow_credentials: Some(credentials),
+            ..Default::default()
+        }
+    }
+
+    async fn allowed_origin(cors_config: &CorsConfig, origin: &str) -> Option<String> {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(cors_layer(cors_config));
+        let req = Request::get("/")
+            .header(ORIGIN, origin)
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        resp.headers()
+            .get(ACCESS_CONTROL_ALLOW_ORIGIN)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn wildcard_patterns() {
+        let cases = [
+            ("https://example.com", "https://example.com", true),
+            ("https://example.com", "https://example.com.evil.io", false),
+            ("https://*.example.com", "https://api.example.com", true),
+            ("https://*.example.com", "https://a.b.example.com", true),
+            ("https://*.example.com", "https://example.com", false),
+            (
+                "https://*.example.com",
+                "https://api.example.com.evil.io",
+                false,
+            ),
+            ("https://*.example.com", "http://api.example.com", false),
+            ("http://localhost:*", "http://localhost:3000", true),
+            ("https://*.*.example.com", "https://a.b.example.com", true),
+            ("https://*.*.example.com", "https://a.example.com", false),
+            ("https://*example.com*", "https://example.co", false),
+        ];
+        for (pattern, origin, matched) in cases {
+            assert_eq!(
+                wildcard_match(pattern, origin),
+                matched,
+                "{} {}",
+                pattern,
+                origin
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn allow_origin_list_and_patterns() {
+        let cors = config(&["https://example.com", "https://*.example.io"], true);
+        assert_eq!(
+            allowed_origin(&cors, "https://example.com")
+                .await
+                .as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            allowed_origin(&cors, "https://api.example.io")
+                .await
+                .as_deref(),
+            Some("https://api.example.io")
+        );
+        assert_eq!(allowed_origin(&cors, "https://evil.io").await, None);
+
+        let cors = config(&["https://example.com"], false);
+        assert_eq!(allowed_origin(&cors, "https://evil.io").await, None);
+    }
+
+    #[tokio::test]
+    async fn allow_any_origin_without_credentials() {
+        let cors = config(&["*"], false);
+        assert_eq!(
+            allowed_origin(&cors, "https://evil.io").await.as_deref(),
+            Some("*")
+        );
+
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(cors_layer(&config(&["https://example.com"], true)));
+        let req = Request::get("/")
+            .header(ORIGIN, "https://example.com")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()[ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    }
+
+    #[test]
+    #[should_panic(expected = "allow_credentials")]
+    fn reject_any_origin_with_credentials() {
+        let _ = cors_layer(&config(&["*"], true));
+    }
+
+    #[test]
+    fn reject_any_with_credentials() {
+        let mut cors = config(&["https://example.com"], true);
+        assert!(cors.validate().is_ok());
+        cors.allow_headers = Some(vec!["*".to_string()]);
+        assert!(cors.validate().is_err());
+        cors.allow_headers = Some(vec!["content-type".to_string()]);
+        cors.allow_methods = Some(vec!["*".to_string()]);
+        assert!(cors.validate().is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use nano_rs_core::config::ip_filter::IpFilterConfig;
+
+use crate::axum::rest::RestResp;
+use crate::axum::utils::client_ip;
+
+/// ip network, "10.0.0.0/8", "2001:db8::/32", a bare ip is a single host
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cidr {
+    pub addr: IpAddr,
+    pub prefix: u8,
+}
+
+impl Cidr {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        // "::ffff:10.0.0.1" behind dual stack listeners
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(*ip),
+            IpAddr::V4(_) => *ip,
+        };
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_eq(u32::from(net) as u128, u32::from(ip) as u128, self.prefix, 32)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_eq(u128::from(net), u128::from(ip), self.prefix, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_eq(net: u128, ip: u128, prefix: u8, bits: u8) -> bool {
+    if prefix == 0 {
+        return true;
+    }
+    let shift = (bits - prefix) as u32;
+    net >> shift == ip >> shift
+}
+
+impl FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("invalid cidr address {:?}", s))?;
+        let bits = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= bits)
+                .ok_or(format!("invalid cidr prefix {:?}", s))?,
+            None => bits,
+        };
+        Ok(Cidr { addr, prefix })
+    }
+}
+
+/// allow and deny lists, deny wins, an empty allow list allows all
+#[derive(Debug, Clone, Default)]
+pub struct IpRule {
+    pub allow: Vec<Cidr>,
+    pub deny: Vec<Cidr>,
+}
+
+impl IpRule {
+    pub fn new(allow: &[String], deny: &[String]) -> Self {
+        IpRule {
+            allow: parse_cidrs(allow),
+            deny: parse_cidrs(deny),
+        }
+    }
+
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        if self.deny.iter().any(|cidr| cidr.contains(ip)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|cidr| cidr.contains(ip))
+    }
+}
+
+fn parse_cidrs(cidrs: &[String]) -> Vec<Cidr> {
+    cidrs
+        .iter()
+        .map(|cidr| cidr.parse().unwrap_or_else(|e| panic!("ip filter: {}", e)))
+        .collect()
+}
+
+/// ip 过滤器
+/// Global and per route path ip rules, use it with `ip_filter` middleware
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use axum::routing::get;
+/// use axum::Router;
+/// use nano_rs_extra::axum::middleware::ip_filter::{ip_filter, IpFilter, IpRule};
+///
+/// // admin routes only from the office network
+/// let filter = IpFilter::default().route("/admin", IpRule::new(&["10.1.0.0/16".to_string()], &[]));
+/// let app: Router = Router::new()
+///     .route("/admin/users", get(|| async { "users" }))
+///     .layer(axum::middleware::from_fn_with_state(Arc::new(filter), ip_filter));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IpFilter {
+    pub global: IpRule,
+    /// (path prefix, rule)
+    pub routes: Vec<(String, IpRule)>,
+}
+
+impl IpFilter {
+    pub fn new(global: IpRule) -> Self {
+        IpFilter {
+            global,
+            routes: vec![],
+        }
+    }
+
+    pub fn from_config(ip_filter_config: &IpFilterConfig) -> Self {
+        let global = IpRule::new(
+            ip_filter_config.allow.as_deref().unwrap_or_default(),
+            ip_filter_config.deny.as_deref().unwrap_or_default(),
+        );
+        let mut filter = IpFilter::new(global);
+        for route in ip_filter_config.routes.clone().unwrap_or_default() {
+            filter = filter.route(
+                route.path.as_str(),
+                IpRule::new(
+                    route.allow.as_deref().unwrap_or_default(),
+                    route.deny.as_deref().unwrap_or_default(),
+                ),
+            );
+        }
+        filter
+    }
+
+    /// rule for requests under path prefix
+    pub fn route(mut self, path: &str, rule: IpRule) -> Self {
+        self.routes
+            .push((format!("/{}", path.trim().trim_matches('/')), rule));
+        self
+    }
+
+    /// global rule and every rule whose path prefix matches must allow the ip
+    pub fn is_allowed(&self, path: &str, ip: &IpAddr) -> bool {
+        self.global.is_allowed(ip)
+            && self
+                .routes
+                .iter()
+                .filter(|(prefix, _)| path_matches(prefix, path))
+                .all(|(_, rule)| rule.is_allowed(ip))
+    }
+}
+
+fn path_matches(prefix: &str, path: &str) -> bool {
+    prefix == "/"
+        || path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// ip filter middleware, rejects with 403 `RestResp`; requests without a resolvable ip are rejected
+pub async fn ip_filter(State(filter): State<Arc<IpFilter>>, req: Request, next: Next) -> Response {
+    let (mut parts, body) = req.into_parts();
+    let ip = client_ip(&mut parts).await;
+    let path = parts.uri.path();
+    match ip {
+        Some(ip) if filter.is_allowed(path, &ip) => next.run(Request::from_parts(parts, body)).await,
+        _ => {
+            tracing::warn!(
+                "ip filter rejected: ip:{} path:{}",
+                ip.map(|ip| ip.to_string()).unwrap_or_default(),
+                path
+            );
+            RestResp::<()> {
+                code: 403,
+                msg: "FORBIDDEN".to_string(),
+                data: None,
+                status: Some(StatusCode::FORBIDDEN),
+            }
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::ConnectInfo;
+    use axum::routing::get;
+    use axum::Router;
+    use axum_client_ip::ClientIpSource;
+    use std::net::SocketAddr;
+    use tower::ServiceExt;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    fn rule(allow: &[&str], deny: &[&str]) -> IpRule {
+        let strings = |cidrs: &[&str]| {
+            cidrs
+                .iter()
+                .map(|cidr| cidr.to_string())
+                .collect::<Vec<_>>()
+        };
+        IpRule::new(&strings(allow), &strings(deny))
+    }
+
+    #[test]
+    fn parse_cidr() {
+        assert_eq!(
+            "10.0.0.0/8".parse::<Cidr>(),
+            Ok(Cidr {
+                addr: ip("10.0.0.0"),
+                prefix: 8
+            })
+        );
+        assert_eq!("10.1.2.3".parse::<Cidr>().unwrap().prefix, 32);
+        assert_eq!("2001:db8::/32".parse::<Cidr>().unwrap().prefix, 32);
+        assert_eq!("::1".parse::<Cidr>().unwrap().prefix, 128);
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+        assert!("2001:db8::/129".parse::<Cidr>().is_err());
+        assert!("10.0.0/8".parse::<Cidr>().is_err());
+        assert!("10.0.0.0/x".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn cidr_contains() {
+        let net: Cidr = "10.1.0.0/16".parse().unwrap();
+        assert!(net.contains(&ip("10.1.0.1")));
+        assert!(net.contains(&ip("10.1.255.255")));
+        assert!(!net.contains(&ip("10.2.0.1")));
+        assert!(net.contains(&ip("::ffff:10.1.0.1")));
+        assert!(!net.contains(&ip("2001:db8::1")));
+
+        let net: Cidr = "2001:db8::/32".parse().unwrap();
+        assert!(net.contains(&ip("2001:db8:ffff::1")));
+        assert!(!net.contains(&ip("2001:db9::1")));
+
+        let any: Cidr = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(&ip("192.168.1.1")));
+        let host: Cidr = "192.168.1.1".parse().unwrap();
+        assert!(host.contains(&ip("192.168.1.1")));
+        assert!(!host.contains(&ip("192.168.1.2")));
+    }
+
+    #[test]
+    fn allow_and_deny() {
+        let open = rule(&[], &[]);
+        assert!(open.is_allowed(&ip("8.8.8.8")));
+
+        let office = rule(&["10.0.0.0/8"], &["10.0.0.13"]);
+        assert!(office.is_allowed(&ip("10.0.0.1")));
+        assert!(!office.is_allowed(&ip("10.0.0.13")));
+        assert!(!office.is_allowed(&ip("8.8.8.8")));
+
+        let blocked = rule(&[], &["192.168.0.0/16"]);
+        assert!(!blocked.is_allowed(&ip("192.168.3.4")));
+        assert!(blocked.is_allowed(&ip("8.8.8.8")));
+    }
+
+    #[test]
+    fn route_rules() {
+        let filter =
+            IpFilter::new(rule(&[], &["1.2.3.4"])).route("/admin/", rule(&["10.0.0.0/8"], &[]));
+        assert!(filter.is_allowed("/admin", &ip("10.0.0.1")));
+        assert!(filter.is_allowed("/admin/users", &ip("10.0.0.1")));
+        assert!(!filter.is_allowed("/admin/users", &ip("8.8.8.8")));
+        assert!(filter.is_allowed("/administrator", &ip("8.8.8.8")));
+        assert!(filter.is_allowed("/pets", &ip("8.8.8.8")));
+        assert!(!filter.is_allowed("/pets", &ip("1.2.3.4")));
+    }
+
+    async fn status(
+        source: Option<ClientIpSource>,
+        real_ip: Option<&str>,
+        peer: &str,
+    ) -> StatusCode {
+        let filter = IpFilter::new(rule(&["10.0.0.0/8"], &[]));
+        let mut app = Router::new().route("/", get(|| async { "ok" })).layer(
+            axum::middleware::from_fn_with_state(Arc::new(filter), ip_filter),
+        );
+        if let Some(source) = source {
+            app = app.layer(source.into_extension());
+        }
+        let mut req = Request::get("/");
+        if let Some(real_ip) = real_ip {
+            req = req.header("x-real-ip", real_ip);
+        }
+        let mut req = req.body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::new(ip(peer), 4000)));
+        app.oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn filter_client_ip() {
+        // no source, the peer address decides
+        assert_eq!(status(None, None, "10.0.0.1").await, StatusCode::OK);
+        assert_eq!(status(None, None, "8.8.8.8").await, StatusCode::FORBIDDEN);
+        // behind a proxy in the allowed range, the header decides
+        let source = || Some(ClientIpSource::XRealIp);
+        assert_eq!(
+            status(source(), Some("10.0.0.2"), "10.0.0.1").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(source(), Some("8.8.8.8"), "10.0.0.1").await,
+            StatusCode::FORBIDDEN
+        );
+        // a missing header never falls back to the proxy address
+        assert_eq!(
+            status(source(), None, "10.0.0.1").await,
+            StatusCode::FORBIDDEN
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower::load_shed::error::Overloaded;
+use tower::BoxError;
+
+use crate::axum::middleware::prometheus::http_metrics;
+use crate::axum::rest::RestResp;
+
+/// error handler of the load shed stack, overloaded requests get a 503 `RestResp`
+/// and are counted in `http_requests_shed_total`
+pub async fn handle_overloaded(err: BoxError) -> Response {
+    if err.is::<Overloaded>() {
+        http_metrics().requests_shed_total.inc();
+        tracing::warn!("request shed, server overloaded");
+        return RestResp::<()> {
+            code: 503,
+            msg: "SERVICE_UNAVAILABLE".to_string(),
+            data: None,
+            status: Some(StatusCode::SERVICE_UNAVAILABLE),
+        }
+        .into_response();
+    }
+    tracing::error!("unhandled middleware error: {}", err);
+    RestResp::<()> {
+        code: 500,
+        msg: "INTERNAL_SERVER_ERROR".to_string(),
+        data: None,
+        status: Some(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+    .into_response()
+}
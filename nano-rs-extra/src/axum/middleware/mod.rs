@@ -1,3 +1,13 @@
+pub mod auth;
+pub mod compression;
+pub mod cors;
+pub mod ip_filter;
+pub mod load_shed;
+pub mod panic;
+pub mod permission;
 pub mod prometheus;
+pub mod rate_limit;
+pub mod security_headers;
+pub mod session;
 pub mod trace;
 pub mod trace_with_state;
@@ -0,0 +1,90 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::panic::AssertUnwindSafe;
+use std::sync::Once;
+
+use axum::extract::Request;
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use futures_util::FutureExt;
+
+use crate::axum::rest::RestResp;
+
+/// request id header, read from the request (gateway, `SetRequestIdLayer`) or generated
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+thread_local! {
+    /// location and backtrace of the last panic on this thread, filled by the panic hook
+    static PANIC_INFO: RefCell<Option<(String, Backtrace)>> = const { RefCell::new(None) };
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// record panic location and backtrace for `catch_panic`, the previous hook still runs
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let location = info
+                .location()
+                .map(|location| location.to_string())
+                .unwrap_or_default();
+            PANIC_INFO.with(|cell| {
+                *cell.borrow_mut() = Some((location, Backtrace::force_capture()));
+            });
+            previous(info);
+        }));
+    });
+}
+
+/// catch handler panics, log them with the request id and respond with a 500 `RestResp`
+pub async fn catch_panic(req: Request, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|id| id.to_str().ok())
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let (method, path) = (req.method().to_string(), req.uri().to_string());
+    match AssertUnwindSafe(next.run(req)).catch_unwind().await {
+        Ok(res) => res,
+        Err(payload) => {
+            let (location, backtrace) = PANIC_INFO
+                .with(|cell| cell.borrow_mut().take())
+                .map(|(location, backtrace)| (location, backtrace.to_string()))
+                .unwrap_or_default();
+            tracing::error!(
+                "PANIC: request_id:{} method:{} path:{} payload:{} location:{} - {}",
+                request_id,
+                method,
+                path,
+                panic_message(payload.as_ref()),
+                location,
+                backtrace
+            );
+            let mut res = RestResp::<()> {
+                code: 500,
+                msg: "INTERNAL_SERVER_ERROR".to_string(),
+                data: None,
+                status: Some(StatusCode::INTERNAL_SERVER_ERROR),
+            }
+            .into_response();
+            if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+                res.headers_mut().insert(X_REQUEST_ID, value);
+            }
+            res
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
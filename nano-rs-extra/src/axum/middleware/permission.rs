@@ -0,0 +1,266 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+use axum::extract::{Request, State};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde_json::Value;
+
+use crate::axum::i18n;
+use crate::axum::middleware::auth::VerifiedClaims;
+use crate::axum::rest::RestResp;
+
+/// 路由权限要求
+/// Permission and roles required by a route, from `permission = ".."` and `roles = [..]` of the route macro
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Requirement {
+    pub permission: Option<&'static str>,
+    /// any of them, empty means no role required
+    pub roles: &'static [&'static str],
+}
+
+impl Requirement {
+    pub const fn new(permission: Option<&'static str>, roles: &'static [&'static str]) -> Self {
+        Requirement { permission, roles }
+    }
+}
+
+/// roles and permissions of the request user
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Grants {
+    pub roles: Vec<String>,
+    /// `*` grants everything, `pet:*` grants every `pet:` permission
+    pub permissions: Vec<String>,
+}
+
+impl Grants {
+    pub fn new(roles: Vec<String>, permissions: Vec<String>) -> Self {
+        Grants { roles, permissions }
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|granted| granted == role)
+    }
+
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|granted| match granted.strip_suffix('*') {
+            Some(prefix) => permission.starts_with(prefix),
+            None => granted == permission,
+        })
+    }
+
+    pub fn satisfies(&self, requirement: &Requirement) -> bool {
+        let role_ok = requirement.roles.is_empty() || requirement.roles.iter().any(|role| self.has_role(role));
+        let permission_ok = requirement
+            .permission
+            .map_or(true, |permission| self.has_permission(permission));
+        role_ok && permission_ok
+    }
+}
+
+/// boxed future returned by [`PermissionResolver::resolve`]
+pub type GrantsFuture<'a> = Pin<Box<dyn Future<Output = Grants> + Send + 'a>>;
+
+/// 权限解析
+/// Resolve grants of the request user, implement it to load roles from a database etc..
+///
+/// runs after `require_auth`, so `VerifiedClaims` is in `parts.extensions` when auth is configured
+///
+/// # Example
+/// ```rust
+/// use axum::http::request::Parts;
+/// use nano_rs_extra::axum::middleware::auth::VerifiedClaims;
+/// use nano_rs_extra::axum::middleware::permission::{set_permission_resolver, Grants, GrantsFuture, PermissionResolver};
+///
+/// struct AdminResolver;
+///
+/// impl PermissionResolver for AdminResolver {
+///     fn resolve<'a>(&'a self, parts: &'a Parts) -> GrantsFuture<'a> {
+///         Box::pin(async move {
+///             match parts.extensions.get::<VerifiedClaims>() {
+///                 Some(claims) if claims.0["sub"] == "admin" => Grants::new(vec![], vec!["*".to_string()]),
+///                 _ => Grants::default(),
+///             }
+///         })
+///     }
+/// }
+///
+/// set_permission_resolver(AdminResolver);
+/// ```
+pub trait PermissionResolver: Send + Sync {
+    fn resolve<'a>(&'a self, parts: &'a Parts) -> GrantsFuture<'a>;
+}
+
+/// default resolver, grants from the `roles` and `permissions` (or space separated `scope`) claims
+#[derive(Debug, Clone, Default)]
+pub struct ClaimsResolver;
+
+impl ClaimsResolver {
+    pub fn grants(claims: &Value) -> Grants {
+        let strings = |value: &Value| -> Vec<String> {
+            match value {
+                Value::Array(values) => values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect(),
+                Value::String(value) => value.split_whitespace().map(str::to_string).collect(),
+                _ => vec![],
+            }
+        };
+        let permissions = match claims.get("permissions") {
+            Some(permissions) => strings(permissions),
+            None => claims.get("scope").map(strings).unwrap_or_default(),
+        };
+        Grants::new(claims.get("roles").map(strings).unwrap_or_default(), permissions)
+    }
+}
+
+impl PermissionResolver for ClaimsResolver {
+    fn resolve<'a>(&'a self, parts: &'a Parts) -> GrantsFuture<'a> {
+        let grants = parts
+            .extensions
+            .get::<VerifiedClaims>()
+            .map(|claims| ClaimsResolver::grants(&claims.0))
+            .unwrap_or_default();
+        Box::pin(async move { grants })
+    }
+}
+
+static RESOLVER: RwLock<Option<Arc<dyn PermissionResolver>>> = RwLock::new(None);
+
+/// set resolver of `require_permission`, `ClaimsResolver` is used when none is set
+pub fn set_permission_resolver<R: PermissionResolver + 'static>(resolver: R) {
+    *RESOLVER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(resolver));
+}
+
+pub fn permission_resolver() -> Arc<dyn PermissionResolver> {
+    RESOLVER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| Arc::new(ClaimsResolver))
+}
+
+/// 403 `RestResp`, msg localized by the `auth.forbidden` key
+pub fn forbidden() -> Response {
+    let msg = i18n::lookup("auth.forbidden", &[]).unwrap_or("permission denied".to_string());
+    RestResp::<()> {
+        code: 403,
+        msg,
+        data: None,
+        status: Some(StatusCode::FORBIDDEN),
+    }
+    .into_response()
+}
+
+/// 权限中间件
+/// Reject with 403 `RestResp` unless the resolved grants satisfy the route requirement
+///
+/// generated routes with `permission` or `roles` get this layer inside `require_auth`
+pub async fn require_permission(State(requirement): State<Requirement>, req: Request, next: Next) -> Response {
+    let (parts, body) = req.into_parts();
+    let grants = permission_resolver().resolve(&parts).await;
+    if !grants.satisfies(&requirement) {
+        tracing::debug!("forbidden {} {}: requires {:?}", parts.method, parts.uri.path(), requirement);
+        return forbidden();
+    }
+    next.run(Request::from_parts(parts, body)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::Router;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn grants(roles: &[&str], permissions: &[&str]) -> Grants {
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        Grants::new(strings(roles), strings(permissions))
+    }
+
+    #[test]
+    fn exact_and_wildcard_permissions() {
+        let user = grants(&[], &["pet:read", "store:*"]);
+        assert!(user.has_permission("pet:read"));
+        assert!(!user.has_permission("pet:write"));
+        assert!(!user.has_permission("pet:read:all"));
+        assert!(user.has_permission("store:write"));
+        assert!(user.has_permission("store:order:cancel"));
+        assert!(!user.has_permission("storefront:write"));
+
+        let admin = grants(&[], &["*"]);
+        assert!(admin.has_permission("pet:write"));
+        assert!(!grants(&[], &[]).has_permission("pet:read"));
+    }
+
+    #[test]
+    fn roles_and_permission() {
+        let requirement = Requirement::new(Some("pet:write"), &["groomer", "admin"]);
+        assert!(grants(&["admin"], &["pet:*"]).satisfies(&requirement));
+        assert!(grants(&["guest", "groomer"], &["pet:write"]).satisfies(&requirement));
+        // both are required
+        assert!(!grants(&["admin"], &["pet:read"]).satisfies(&requirement));
+        assert!(!grants(&["guest"], &["*"]).satisfies(&requirement));
+        // a permission is not a role
+        assert!(!grants(&[], &["admin", "pet:write"]).satisfies(&requirement));
+
+        assert!(grants(&["groomer"], &[]).satisfies(&Requirement::new(None, &["groomer"])));
+        assert!(grants(&[], &["pet:write"]).satisfies(&Requirement::new(Some("pet:write"), &[])));
+        assert!(grants(&[], &[]).satisfies(&Requirement::new(None, &[])));
+    }
+
+    #[test]
+    fn grants_from_claims() {
+        let claims = json!({"roles": ["admin"], "permissions": ["pet:write"], "scope": "pet:read"});
+        assert_eq!(
+            ClaimsResolver::grants(&claims),
+            grants(&["admin"], &["pet:write"])
+        );
+        let claims = json!({"roles": "admin groomer", "scope": "pet:read store:*"});
+        assert_eq!(
+            ClaimsResolver::grants(&claims),
+            grants(&["admin", "groomer"], &["pet:read", "store:*"])
+        );
+        assert_eq!(
+            ClaimsResolver::grants(&json!({"roles": 1})),
+            Grants::default()
+        );
+    }
+
+    async fn call(claims: Option<Value>) -> StatusCode {
+        const REQUIREMENT: Requirement = Requirement::new(Some("pet:write"), &[]);
+        let app = Router::new().route(
+            "/pet",
+            get(|| async { "ok" }).route_layer(axum::middleware::from_fn_with_state(
+                REQUIREMENT,
+                require_permission,
+            )),
+        );
+        let mut req = Request::get("/pet").body(Body::empty()).unwrap();
+        if let Some(claims) = claims {
+            req.extensions_mut().insert(VerifiedClaims(claims));
+        }
+        app.oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn require_permission_of_claims() {
+        assert_eq!(
+            call(Some(json!({"permissions": ["pet:*"]}))).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(Some(json!({"permissions": ["pet:read"]}))).await,
+            StatusCode::FORBIDDEN
+        );
+        // fail closed without claims
+        assert_eq!(call(None).await, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use prometheus::{
+    register_histogram_vec, register_int_counter, register_int_counter_vec, Encoder,
+    HistogramVec, IntCounter, IntCounterVec, TextEncoder,
+};
+
+/// http metrics registered in prometheus default registry
+pub struct HttpMetrics {
+    /// http_requests_total{method,path,status}
+    pub requests_total: IntCounterVec,
+    /// http_request_duration_seconds{method,path}
+    pub request_duration: HistogramVec,
+    /// http_requests_shed_total, requests rejected by load shedding
+    pub requests_shed_total: IntCounter,
+}
+
+static HTTP_METRICS: OnceLock<HttpMetrics> = OnceLock::new();
+
+pub fn http_metrics() -> &'static HttpMetrics {
+    HTTP_METRICS.get_or_init(|| HttpMetrics {
+        requests_total: register_int_counter_vec!(
+            "http_requests_total",
+            "Total number of http requests",
+            &["method", "path", "status"]
+        )
+        .expect("register http_requests_total failed"),
+        request_duration: register_histogram_vec!(
+            "http_request_duration_seconds",
+            "Http request latencies in seconds",
+            &["method", "path"]
+        )
+        .expect("register http_request_duration_seconds failed"),
+        requests_shed_total: register_int_counter!(
+            "http_requests_shed_total",
+            "Total number of http requests rejected by load shedding"
+        )
+        .expect("register http_requests_shed_total failed"),
+    })
+}
+
+/// record request count and latency, keyed by the matched route path
+pub async fn track_metrics(req: Request, next: Next) -> impl IntoResponse {
+    let start = Instant::now();
+    let path = if let Some(matched_path) = req.extensions().get::<MatchedPath>() {
+        matched_path.as_str().to_owned()
+    } else {
+        req.uri().path().to_owned()
+    };
+    let method = req.method().to_string();
+
+    let res = next.run(req).await;
+
+    let metrics = http_metrics();
+    metrics
+        .requests_total
+        .with_label_values(&[method.as_str(), path.as_str(), res.status().as_str()])
+        .inc();
+    metrics
+        .request_duration
+        .with_label_values(&[method.as_str(), path.as_str()])
+        .observe(start.elapsed().as_secs_f64());
+    res
+}
+
+/// render prometheus default registry with text format
+pub fn render_metrics() -> String {
+    let mut buffer = vec![];
+    if let Err(err) = TextEncoder::new().encode(&prometheus::gather(), &mut buffer) {
+        tracing::error!("encode metrics failed: {}", err);
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+/// prometheus scrape handler
+pub async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            prometheus::TEXT_FORMAT,
+        )],
+        render_metrics(),
+    )
+}
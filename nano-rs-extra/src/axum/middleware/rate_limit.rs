@@ -0,0 +1,542 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use axum::extract::{Request, State};
+use axum::http::request::Parts;
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use nano_rs_core::config::rate_limit::RateLimitConfig;
+
+use crate::axum::rest::RestResp;
+use crate::axum::utils::client_ip;
+
+/// default buckets kept by `MemoryBackend`
+const MEMORY_BACKEND_MAX_KEYS: usize = 100_000;
+
+/// `ClientIp` key of requests without a resolvable ip
+const UNKNOWN_CLIENT_IP: &str = "unknown";
+
+/// locks of `MemoryBackend`, a full shard is pruned alone
+const MEMORY_BACKEND_SHARDS: usize = 16;
+
+/// request rate, parsed from "100/min", "10/s", "5000/hour", "20/5m"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rate {
+    pub count: u32,
+    pub per: Duration,
+}
+
+impl Rate {
+    /// tokens refilled per second
+    pub fn per_second(&self) -> f64 {
+        self.count as f64 / self.per.as_secs_f64()
+    }
+}
+
+impl FromStr for Rate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (count, period) = s
+            .split_once('/')
+            .ok_or(format!("invalid rate {:?}, expected e.g. \"100/min\"", s))?;
+        let count: u32 = count
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid rate count in {:?}", s))?;
+        let period = period.trim();
+        let unit_start = period
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or(format!("missing rate unit in {:?}", s))?;
+        let multiple: u64 = if unit_start == 0 {
+            1
+        } else {
+            period[..unit_start]
+                .parse()
+                .map_err(|_| format!("invalid rate period in {:?}", s))?
+        };
+        let unit_secs = match &period[unit_start..] {
+            "s" | "sec" | "second" => 1,
+            "m" | "min" | "minute" => 60,
+            "h" | "hour" => 60 * 60,
+            "d" | "day" => 24 * 60 * 60,
+            unit => return Err(format!("unknown rate unit {:?} in {:?}", unit, s)),
+        };
+        if count == 0 || multiple == 0 {
+            return Err(format!("rate must be positive: {:?}", s));
+        }
+        Ok(Rate {
+            count,
+            per: Duration::from_secs(multiple * unit_secs),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitDecision {
+    Allowed,
+    Limited { retry_after: Duration },
+}
+
+/// boxed future returned by [`RateLimitBackend::acquire`]
+pub type RateLimitFuture<'a> = Pin<Box<dyn Future<Output = RateLimitDecision> + Send + 'a>>;
+
+/// 限流存储
+/// Token bucket storage, implement it for distributed backends (redis etc..)
+pub trait RateLimitBackend: Send + Sync {
+    /// take one token from the bucket of `key`
+    fn acquire<'a>(&'a self, key: &'a str, rate: Rate, burst: u32) -> RateLimitFuture<'a>;
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    per_second: f64,
+    burst: f64,
+}
+
+impl Bucket {
+    /// refilled to the burst, dropping it changes nothing
+    fn is_idle(&self, now: Instant) -> bool {
+        self.tokens + now.duration_since(self.updated).as_secs_f64() * self.per_second >= self.burst
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.burst);
+        self.updated = now;
+    }
+}
+
+/// in-process token buckets, sharded by key and bounded by `max_keys`
+///
+/// a full shard drops its idle (refilled) buckets, then the least recently used eighth when all
+/// of them are live, so an evicted key starts again with a full bucket
+pub struct MemoryBackend {
+    shards: Vec<Mutex<HashMap<String, Bucket>>>,
+    shard_capacity: usize,
+    hasher: RandomState,
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        MemoryBackend::with_max_keys(MEMORY_BACKEND_MAX_KEYS)
+    }
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        MemoryBackend::default()
+    }
+
+    /// keep at most about `max_keys` buckets, default 100 000
+    pub fn with_max_keys(max_keys: usize) -> Self {
+        MemoryBackend {
+            shards: (0..MEMORY_BACKEND_SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+            shard_capacity: max_keys.div_ceil(MEMORY_BACKEND_SHARDS).max(1),
+            hasher: RandomState::new(),
+        }
+    }
+
+    fn evict(&self, buckets: &mut HashMap<String, Bucket>, now: Instant) {
+        buckets.retain(|_, bucket| !bucket.is_idle(now));
+        if buckets.len() < self.shard_capacity {
+            return;
+        }
+        let mut updated: Vec<Instant> = buckets.values().map(|bucket| bucket.updated).collect();
+        let evicted = (updated.len() / 8).max(1);
+        let (_, newest_evicted, _) = updated.select_nth_unstable(evicted - 1);
+        let newest_evicted = *newest_evicted;
+        buckets.retain(|_, bucket| bucket.updated > newest_evicted);
+    }
+
+    fn acquire_at(&self, key: &str, rate: Rate, burst: u32, now: Instant) -> RateLimitDecision {
+        let shard = self.hasher.hash_one(key) as usize % self.shards.len();
+        let mut buckets = self.shards[shard].lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= self.shard_capacity && !buckets.contains_key(key) {
+            self.evict(&mut buckets, now);
+        }
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: burst as f64,
+            updated: now,
+            per_second: rate.per_second(),
+            burst: burst as f64,
+        });
+        bucket.refill(now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            RateLimitDecision::Allowed
+        } else {
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.per_second),
+            }
+        }
+    }
+}
+
+impl RateLimitBackend for MemoryBackend {
+    fn acquire<'a>(&'a self, key: &'a str, rate: Rate, burst: u32) -> RateLimitFuture<'a> {
+        Box::pin(std::future::ready(self.acquire_at(key, rate, burst, Instant::now())))
+    }
+}
+
+/// custom key extractor, e.g. an api token header; `None` skips limiting
+pub type KeyExtractor = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+/// what a bucket is keyed on
+#[derive(Clone)]
+pub enum RateLimitKey {
+    /// `ClientIp` resolved by `add_secure_client_ip_source_layer`, else the peer address,
+    /// requests without a resolvable ip share one bucket
+    ClientIp,
+    Custom(KeyExtractor),
+}
+
+impl RateLimitKey {
+    async fn extract(&self, parts: &mut Parts) -> Option<String> {
+        match self {
+            RateLimitKey::ClientIp => Some(
+                client_ip(parts)
+                    .await
+                    .map(|ip| ip.to_string())
+                    .unwrap_or(UNKNOWN_CLIENT_IP.to_string()),
+            ),
+            RateLimitKey::Custom(extractor) => extractor(parts),
+        }
+    }
+}
+
+static DEFAULT_BACKEND: OnceLock<Arc<dyn RateLimitBackend>> = OnceLock::new();
+
+static DEFAULT_KEY: OnceLock<RateLimitKey> = OnceLock::new();
+
+/// set backend used by config and route macro limiters, call it before building routes
+pub fn set_default_backend(backend: Arc<dyn RateLimitBackend>) -> Result<(), String> {
+    DEFAULT_BACKEND
+        .set(backend)
+        .map_err(|_| "rate limit backend already set".to_string())
+}
+
+/// default backend, in memory unless `set_default_backend` was called
+pub fn default_backend() -> Arc<dyn RateLimitBackend> {
+    DEFAULT_BACKEND
+        .get_or_init(|| Arc::new(MemoryBackend::new()))
+        .clone()
+}
+
+/// set key used by config and route macro limiters, call it before building routes
+pub fn set_default_key(key: RateLimitKey) -> Result<(), String> {
+    DEFAULT_KEY
+        .set(key)
+        .map_err(|_| "rate limit key already set".to_string())
+}
+
+/// default key, `ClientIp` unless `set_default_key` was called
+pub fn default_key() -> RateLimitKey {
+    DEFAULT_KEY.get_or_init(|| RateLimitKey::ClientIp).clone()
+}
+
+/// 限流器
+/// Token bucket rate limiter, use it with `rate_limit` middleware
+///
+/// # Example
+/// ```rust
+/// use axum::routing::get;
+/// use axum::Router;
+/// use nano_rs_extra::axum::middleware::rate_limit::{rate_limit, RateLimiter};
+///
+/// let limiter = RateLimiter::new("10/s".parse().unwrap()).burst(20);
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "hello" }))
+///     .route_layer(axum::middleware::from_fn_with_state(limiter, rate_limit));
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    /// bucket namespace, e.g. "global" or "GET /store/name"
+    pub scope: String,
+    pub rate: Rate,
+    pub burst: u32,
+    pub backend: Arc<dyn RateLimitBackend>,
+    pub key: RateLimitKey,
+}
+
+impl RateLimiter {
+    pub fn new(rate: Rate) -> Self {
+        RateLimiter {
+            scope: "global".to_string(),
+            rate,
+            burst: rate.count,
+            backend: default_backend(),
+            key: default_key(),
+        }
+    }
+
+    /// limiter of a single route, used by the code generated for `rate_limit = "100/min"`
+    pub fn per_route(rate: &str, scope: &str) -> Self {
+        let rate = rate
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid rate_limit of {}: {}", scope, e));
+        RateLimiter::new(rate).scope(scope)
+    }
+
+    pub fn from_config(rate_limit_config: &RateLimitConfig) -> Self {
+        let rate: Rate = rate_limit_config
+            .rate
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid rate_limit config: {}", e));
+        RateLimiter::new(rate).burst(rate_limit_config.burst.unwrap_or(rate.count))
+    }
+
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scope = scope.to_string();
+        self
+    }
+
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    pub fn backend(mut self, backend: Arc<dyn RateLimitBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn key(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+}
+
+/// rate limit middleware, rejects with 429 `RestResp` and `Retry-After`
+pub async fn rate_limit(State(limiter): State<RateLimiter>, req: Request, next: Next) -> Response {
+    let (mut parts, body) = req.into_parts();
+    let Some(key) = limiter.key.extract(&mut parts).await else {
+        tracing::debug!("rate limit key not found, skip {}", limiter.scope);
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    let bucket_key = format!("{}#{}", limiter.scope, key);
+    match limiter
+        .backend
+        .acquire(bucket_key.as_str(), limiter.rate, limiter.burst)
+        .await
+    {
+        RateLimitDecision::Allowed => next.run(Request::from_parts(parts, body)).await,
+        RateLimitDecision::Limited { retry_after } => {
+            tracing::warn!("rate limited: {}", bucket_key);
+            let retry_after = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            (
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                RestResp::<()> {
+                    code: 429,
+                    msg: "TOO_MANY_REQUESTS".to_string(),
+                    data: None,
+                    status: Some(StatusCode::TOO_MANY_REQUESTS),
+                },
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn rate(rate: &str) -> Rate {
+        rate.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_rate() {
+        assert_eq!(
+            rate("100/min"),
+            Rate {
+                count: 100,
+                per: Duration::from_secs(60)
+            }
+        );
+        assert_eq!(rate("10/s").per, Duration::from_secs(1));
+        assert_eq!(rate(" 5000 / hour ").count, 5000);
+        assert_eq!(rate("20/5m").per, Duration::from_secs(300));
+        assert_eq!(rate("1/day").per, Duration::from_secs(86400));
+        assert_eq!(rate("10/s").per_second(), 10.0);
+        for invalid in [
+            "100", "x/min", "-1/min", "10/", "10/5", "10/week", "0/min", "10/0m",
+        ] {
+            assert!(invalid.parse::<Rate>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn refill_over_time() {
+        let backend = MemoryBackend::new();
+        let now = Instant::now();
+        let rate = rate("1/s");
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, now),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, now),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, now),
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_secs(1)
+            }
+        );
+        // other keys have their own bucket
+        assert_eq!(
+            backend.acquire_at("other", rate, 2, now),
+            RateLimitDecision::Allowed
+        );
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, later),
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(500)
+            }
+        );
+        let later = now + Duration::from_secs(1);
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, later),
+            RateLimitDecision::Allowed
+        );
+        assert!(matches!(
+            backend.acquire_at("k", rate, 2, later),
+            RateLimitDecision::Limited { .. }
+        ));
+        // never refilled above the burst
+        let later = now + Duration::from_secs(60);
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, later),
+            RateLimitDecision::Allowed
+        );
+        assert_eq!(
+            backend.acquire_at("k", rate, 2, later),
+            RateLimitDecision::Allowed
+        );
+        assert!(matches!(
+            backend.acquire_at("k", rate, 2, later),
+            RateLimitDecision::Limited { .. }
+        ));
+    }
+
+    #[test]
+    fn bounded_keys() {
+        let backend = MemoryBackend::with_max_keys(MEMORY_BACKEND_SHARDS * 4);
+        let now = Instant::now();
+        for i in 0..1000 {
+            let key = format!("k{}", i);
+            backend.acquire_at(
+                key.as_str(),
+                rate("1/min"),
+                1,
+                now + Duration::from_millis(i),
+            );
+        }
+        for shard in backend.shards.iter() {
+            assert!(shard.lock().unwrap().len() <= 4);
+        }
+    }
+
+    #[test]
+    fn evict_idle_then_least_recently_used() {
+        let backend = MemoryBackend::with_max_keys(MEMORY_BACKEND_SHARDS * 16);
+        let now = Instant::now();
+        let bucket = |tokens: f64, updated: Instant| Bucket {
+            tokens,
+            updated,
+            per_second: 1.0,
+            burst: 10.0,
+        };
+        let mut buckets: HashMap<String, Bucket> = (0..16u64)
+            .map(|i| {
+                (
+                    format!("live{}", i),
+                    bucket(0.0, now + Duration::from_millis(i)),
+                )
+            })
+            .collect();
+        buckets.insert("idle".to_string(), bucket(10.0, now));
+        backend.evict(&mut buckets, now + Duration::from_millis(100));
+        assert!(!buckets.contains_key("idle"));
+        // all live and the shard is still full, the oldest eighth goes
+        assert_eq!(buckets.len(), 14);
+        assert!(!buckets.contains_key("live0") && !buckets.contains_key("live1"));
+        assert!(buckets.contains_key("live2") && buckets.contains_key("live15"));
+    }
+
+    #[tokio::test]
+    async fn limit_routes_separately() {
+        let backend: Arc<dyn RateLimitBackend> = Arc::new(MemoryBackend::new());
+        let key = RateLimitKey::Custom(Arc::new(|parts: &Parts| {
+            parts
+                .headers
+                .get("x-api-key")
+                .and_then(|key| key.to_str().ok())
+                .map(|key| key.to_string())
+        }));
+        let limiter = |scope: &str| {
+            RateLimiter::per_route("1/min", scope)
+                .backend(backend.clone())
+                .key(key.clone())
+        };
+        let app = Router::new()
+            .route(
+                "/a",
+                get(|| async { "a" }).route_layer(axum::middleware::from_fn_with_state(
+                    limiter("GET /a"),
+                    rate_limit,
+                )),
+            )
+            .route(
+                "/b",
+                get(|| async { "b" }).route_layer(axum::middleware::from_fn_with_state(
+                    limiter("GET /b"),
+                    rate_limit,
+                )),
+            );
+        let call = |path: &str, api_key: Option<&str>| {
+            let mut req = Request::get(path);
+            if let Some(api_key) = api_key {
+                req = req.header("x-api-key", api_key);
+            }
+            app.clone().oneshot(req.body(Body::empty()).unwrap())
+        };
+
+        assert_eq!(
+            call("/a", Some("alice")).await.unwrap().status(),
+            StatusCode::OK
+        );
+        let limited = call("/a", Some("alice")).await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[header::RETRY_AFTER], "60");
+        // the other route and the other key have their own buckets
+        assert_eq!(
+            call("/b", Some("alice")).await.unwrap().status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            call("/a", Some("bob")).await.unwrap().status(),
+            StatusCode::OK
+        );
+        // no key, not limited
+        assert_eq!(call("/a", None).await.unwrap().status(), StatusCode::OK);
+        assert_eq!(call("/a", None).await.unwrap().status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use nano_rs_core::config::security_headers::SecurityHeadersConfig;
+
+/// security headers resolved from `SecurityHeadersConfig`, state of `security_headers`
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    /// set when the handler did not set them itself
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+    /// always stripped from responses
+    pub remove_headers: Vec<HeaderName>,
+}
+
+impl SecurityHeaders {
+    pub fn new(security_headers_config: &SecurityHeadersConfig) -> Self {
+        let mut headers = vec![];
+        let mut push = |name: HeaderName, value: Option<String>| {
+            if let Some(value) = value {
+                let header_value = HeaderValue::from_str(value.as_str())
+                    .unwrap_or_else(|_| panic!("invalid {} header: {}", name, value));
+                headers.push((name, header_value));
+            }
+        };
+        push(header::STRICT_TRANSPORT_SECURITY, security_headers_config.get_hsts());
+        if security_headers_config.content_type_options.unwrap_or(true) {
+            push(header::X_CONTENT_TYPE_OPTIONS, Some("nosniff".to_string()));
+        }
+        push(header::X_FRAME_OPTIONS, security_headers_config.get_frame_options());
+        push(header::REFERRER_POLICY, security_headers_config.get_referrer_policy());
+        push(
+            header::CONTENT_SECURITY_POLICY,
+            security_headers_config.content_security_policy.clone(),
+        );
+        let remove_headers = security_headers_config
+            .get_remove_headers()
+            .iter()
+            .map(|name| {
+                HeaderName::from_bytes(name.trim().as_bytes())
+                    .unwrap_or_else(|_| panic!("invalid remove header: {}", name))
+            })
+            .collect();
+        SecurityHeaders {
+            headers,
+            remove_headers,
+        }
+    }
+}
+
+/// set security headers on every response and strip server identifying ones
+pub async fn security_headers(
+    State(security_headers): State<Arc<SecurityHeaders>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mut res = next.run(req).await;
+    let headers = res.headers_mut();
+    for name in security_headers.remove_headers.iter() {
+        headers.remove(name);
+    }
+    for (name, value) in security_headers.headers.iter() {
+        if !headers.contains_key(name) {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+    res
+}
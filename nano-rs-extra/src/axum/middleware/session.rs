@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::axum::i18n;
+use crate::axum::rest::RestResp;
+use crate::axum::session::SessionManager;
+
+/// 403 `RestResp`, msg localized by the `session.csrf` key
+pub fn csrf_rejected() -> Response {
+    let msg = i18n::lookup("session.csrf", &[]).unwrap_or("invalid csrf token".to_string());
+    RestResp::<()> {
+        code: 403,
+        msg,
+        data: None,
+        status: Some(StatusCode::FORBIDDEN),
+    }
+    .into_response()
+}
+
+/// 会话中间件
+/// Load the `Session` of the cookie, check the csrf token of unsafe methods and save the changed session
+///
+/// `require_auth` accepts the claims of `Session::login` when no bearer token is sent, a login
+/// without the csrf token of the session is not saved and gets a 403
+pub async fn session(State(manager): State<Arc<SessionManager>>, mut req: Request, next: Next) -> Response {
+    let session = manager.load(req.headers()).await;
+    let safe = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE);
+    if !safe && !manager.check_csrf(&session, req.headers()) {
+        tracing::debug!("csrf rejected {} {}", req.method(), req.uri().path());
+        return csrf_rejected();
+    }
+    let csrf_token = manager.has_csrf_token(&session, req.headers());
+    req.extensions_mut().insert(session.clone());
+    let mut res = next.run(req).await;
+    if session.is_login() && !csrf_token {
+        tracing::warn!("login without csrf token rejected");
+        return csrf_rejected();
+    }
+    manager.commit(&session, res.headers_mut()).await;
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::header;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::axum::session::{MemoryStore, Session};
+
+    async fn csrf(session: Session) -> String {
+        session.csrf_token()
+    }
+
+    async fn login(session: Session) -> &'static str {
+        session.login(&json!({"sub": "mantou"})).unwrap();
+        "ok"
+    }
+
+    async fn me(session: Session) -> String {
+        session.claims().map(|claims: Value| claims["sub"].to_string()).unwrap_or_default()
+    }
+
+    fn app() -> Router {
+        let manager = SessionManager::new(b"secret", Arc::new(MemoryStore::new()));
+        Router::new()
+            .route("/csrf", get(csrf))
+            .route("/login", post(login))
+            .route("/me", get(me).post(me))
+            .layer(axum::middleware::from_fn_with_state(Arc::new(manager), session))
+    }
+
+    async fn call(app: &Router, method: Method, uri: &str, cookie: Option<&str>, token: Option<&str>) -> Response {
+        let mut req = Request::builder().method(method).uri(uri);
+        if let Some(cookie) = cookie {
+            req = req.header(header::COOKIE, cookie);
+        }
+        if let Some(token) = token {
+            req = req.header("x-csrf-token", token);
+        }
+        app.clone().oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    /// `name=value` of the `Set-Cookie` header
+    fn cookie(res: &Response) -> Option<String> {
+        let set_cookie = res.headers().get(header::SET_COOKIE)?.to_str().ok()?;
+        set_cookie.split(';').next().map(|pair| pair.to_string())
+    }
+
+    async fn body(res: Response) -> String {
+        let bytes = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    /// session cookie and csrf token of a fresh session
+    async fn csrf_session(app: &Router) -> (String, String) {
+        let res = call(app, Method::GET, "/csrf", None, None).await;
+        let cookie = cookie(&res).unwrap();
+        (cookie, body(res).await)
+    }
+
+    #[tokio::test]
+    async fn unsafe_request_needs_csrf_token() {
+        let app = app();
+        let (cookie, token) = csrf_session(&app).await;
+        let res = call(&app, Method::POST, "/me", Some(cookie.as_str()), None).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res = call(&app, Method::POST, "/me", Some(cookie.as_str()), Some("forged")).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res = call(&app, Method::POST, "/me", Some(cookie.as_str()), Some(token.as_str())).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = call(&app, Method::GET, "/me", Some(cookie.as_str()), None).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn login_without_csrf_token_is_rejected() {
+        let app = app();
+        let res = call(&app, Method::POST, "/login", None, None).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert!(cookie(&res).is_none());
+        let (cookie, _) = csrf_session(&app).await;
+        let res = call(&app, Method::POST, "/login", Some(cookie.as_str()), None).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        assert!(body(call(&app, Method::GET, "/me", Some(cookie.as_str()), None).await).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn login_renews_session_id() {
+        let app = app();
+        let (before, token) = csrf_session(&app).await;
+        let res = call(&app, Method::POST, "/login", Some(before.as_str()), Some(token.as_str())).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let after = cookie(&res).unwrap();
+        assert_ne!(before, after);
+        let res = call(&app, Method::GET, "/me", Some(after.as_str()), None).await;
+        assert_eq!(body(res).await, "\"mantou\"");
+        // the pre-login id is dropped, a fixated session id gains nothing
+        let res = call(&app, Method::GET, "/me", Some(before.as_str()), None).await;
+        assert!(body(res).await.is_empty());
+        // the csrf token moves to the new id
+        let res = call(&app, Method::POST, "/me", Some(after.as_str()), Some(token.as_str())).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn tampered_cookie_starts_a_new_session() {
+        let app = app();
+        let (cookie, token) = csrf_session(&app).await;
+        let tampered = format!("{}x", cookie);
+        let res = call(&app, Method::POST, "/login", Some(tampered.as_str()), Some(token.as_str())).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
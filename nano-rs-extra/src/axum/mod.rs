@@ -1,9 +1,18 @@
+pub mod admin;
+pub mod client;
+pub mod cookie;
 pub mod errors;
 pub mod extractor;
 pub mod generator;
 pub mod handler;
+pub mod health;
+pub mod i18n;
+pub mod page;
 pub mod middleware;
+pub mod problem;
 pub mod rest;
+pub mod session;
 pub mod shutdown;
 pub mod start;
+pub mod static_files;
 pub mod utils;
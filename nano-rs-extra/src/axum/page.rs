@@ -0,0 +1,155 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::extract::{FromRequestParts, Query};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use nano_rs_core::config::page::PageConfig;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::axum::errors::ServerError;
+use crate::axum::extractor::rejection::{rest_rejection, RestRejection};
+use crate::axum::rest::{biz_ok, success_code, RestResp};
+
+static DEFAULT_SIZE: AtomicU64 = AtomicU64::new(10);
+
+static MAX_SIZE: AtomicU64 = AtomicU64::new(100);
+
+/// set default and max page size, `AppStarter` sets it from `RestConfig.page`
+pub fn set_page_config(page_config: &PageConfig) {
+    DEFAULT_SIZE.store(page_config.get_default_size(), Ordering::Relaxed);
+    MAX_SIZE.store(page_config.get_max_size(), Ordering::Relaxed);
+}
+
+/// 分页参数
+/// Page query extractor, `?page=2&size=20` or `?cursor=xxx&size=20`
+///
+/// page starts at 1, size defaults to `PageConfig.default_size` and is clamped to `PageConfig.max_size`
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::errors::ServerError;
+/// use nano_rs_extra::axum::page::{biz_page, PageQuery, PagedResp};
+/// use nano_rs_extra::axum::rest::RestResp;
+///
+/// pub async fn list(page: PageQuery) -> Result<RestResp<PagedResp<String>>, ServerError> {
+///     let items = vec!["mantou".to_string()];
+///     biz_page(items, 1, &page)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct PageQuery {
+    /// page number, start 1
+    pub page: u64,
+    /// page size, default 10
+    pub size: u64,
+    /// cursor of cursor based paging, `next_cursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+impl PageQuery {
+    pub fn new(page: u64, size: u64) -> Self {
+        PageQuery {
+            page: page.max(1),
+            size: size.clamp(1, MAX_SIZE.load(Ordering::Relaxed)),
+            cursor: None,
+        }
+    }
+
+    /// rows skipped before this page, saturated for huge page numbers of the query string
+    pub fn offset(&self) -> u64 {
+        self.page.saturating_sub(1).saturating_mul(self.size)
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Default for PageQuery {
+    fn default() -> Self {
+        PageQuery::new(1, DEFAULT_SIZE.load(Ordering::Relaxed))
+    }
+}
+
+#[derive(Deserialize)]
+struct RawPageQuery {
+    page: Option<u64>,
+    size: Option<u64>,
+    cursor: Option<String>,
+}
+
+impl<S> FromRequestParts<S> for PageQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = RestRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let reject = |msg: String| rest_rejection("page", StatusCode::BAD_REQUEST, msg);
+        let raw = Query::<RawPageQuery>::try_from_uri(&parts.uri)
+            .map_err(|rejection| reject(rejection.body_text()))?
+            .0;
+        if raw.page == Some(0) {
+            return Err(reject("page starts at 1".to_string()));
+        }
+        if raw.size == Some(0) {
+            return Err(reject("size must be positive".to_string()));
+        }
+        let mut page = PageQuery::new(
+            raw.page.unwrap_or(1),
+            raw.size.unwrap_or(DEFAULT_SIZE.load(Ordering::Relaxed)),
+        );
+        page.cursor = raw.cursor.filter(|cursor| !cursor.is_empty());
+        Ok(page)
+    }
+}
+
+/// 分页结果
+/// Paged list, returned as `RestResp.data`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PagedResp<T> {
+    pub items: Vec<T>,
+    /// total count, none for cursor based paging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    /// page number, none for cursor based paging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+    pub size: u64,
+    /// cursor of the next page, none on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> PagedResp<T> {
+    /// page of a page/size query
+    pub fn new(items: Vec<T>, total: u64, page: &PageQuery) -> Self {
+        PagedResp {
+            items,
+            total: Some(total),
+            page: Some(page.page),
+            size: page.size,
+            next_cursor: None,
+        }
+    }
+
+    /// page of a cursor query, `next_cursor` none on the last page
+    pub fn with_cursor(items: Vec<T>, next_cursor: Option<String>, page: &PageQuery) -> Self {
+        PagedResp {
+            items,
+            total: None,
+            page: None,
+            size: page.size,
+            next_cursor,
+        }
+    }
+}
+
+///返回分页数据
+/// Return a page of items with total count
+pub fn biz_page<T>(items: Vec<T>, total: u64, page: &PageQuery) -> Result<RestResp<PagedResp<T>>, ServerError> {
+    biz_ok(success_code(), PagedResp::new(items, total, page))
+}
@@ -0,0 +1,158 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use nano_rs_core::config::rest::ResponseFormat;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::axum::middleware::panic::X_REQUEST_ID;
+use crate::axum::rest::json_response;
+
+/// rfc 7807 content type
+pub const APPLICATION_PROBLEM_JSON: &str = "application/problem+json";
+
+/// rfc 7807 problem document
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Problem {
+    /// problem type uri, default "about:blank"
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    /// short summary, default the http status reason
+    pub title: String,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// request path the problem occurred on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// extension members, e.g. the business `code`
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+impl Problem {
+    /// problem of status, `instance` is the current request path (see `with_response_format`)
+    pub fn new(status: StatusCode) -> Self {
+        Problem {
+            type_uri: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail: None,
+            instance: RESPONSE_CONTEXT.try_with(|context| context.instance.clone()).ok().flatten(),
+            extensions: Map::new(),
+        }
+    }
+
+    pub fn type_uri(mut self, type_uri: &str) -> Self {
+        self.type_uri = type_uri.to_string();
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    pub fn detail(mut self, detail: &str) -> Self {
+        self.detail = Some(detail.to_string());
+        self
+    }
+
+    pub fn instance(mut self, instance: &str) -> Self {
+        self.instance = Some(instance.to_string());
+        self
+    }
+
+    /// business code extension member
+    pub fn code(self, code: i32) -> Self {
+        self.extension("code", code)
+    }
+
+    pub fn extension(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.extensions.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        json_response(status, APPLICATION_PROBLEM_JSON, &self)
+    }
+}
+
+/// format, path and request id of the request being served
+#[derive(Debug, Clone)]
+struct ResponseContext {
+    format: ResponseFormat,
+    instance: Option<String>,
+    request_id: Option<String>,
+}
+
+tokio::task_local! {
+    static RESPONSE_CONTEXT: ResponseContext;
+}
+
+static DEFAULT_FORMAT: AtomicU8 = AtomicU8::new(0);
+
+/// set app wide response format, `AppStarter` sets it from `RestConfig.response_format`
+pub fn set_response_format(format: ResponseFormat) {
+    DEFAULT_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+/// response format of the current request, the route group format when set, else app wide
+pub fn response_format() -> ResponseFormat {
+    RESPONSE_CONTEXT
+        .try_with(|context| context.format)
+        .unwrap_or_else(|_| match DEFAULT_FORMAT.load(Ordering::Relaxed) {
+            1 => ResponseFormat::Problem,
+            2 => ResponseFormat::ProblemUnwrapped,
+            _ => ResponseFormat::Envelope,
+        })
+}
+
+/// request id of the current request (`x-request-id` or generated), used by `EnvelopeConfig.request_id_field`
+pub fn request_id() -> Option<String> {
+    RESPONSE_CONTEXT.try_with(|context| context.request_id.clone()).ok().flatten()
+}
+
+/// response format of a route group, also fills `Problem.instance` with the request path
+/// and the envelope request id, echoed back as `x-request-id`
+///
+/// # Example
+/// ```rust
+/// use axum::routing::get;
+/// use axum::Router;
+/// use nano_rs_extra::axum::problem::with_response_format;
+/// use nano_rs_extra::axum::rest::ResponseFormat;
+///
+/// // public api answers errors with application/problem+json
+/// let public_api: Router = Router::new()
+///     .route("/pets", get(|| async { "pets" }))
+///     .layer(axum::middleware::from_fn_with_state(ResponseFormat::Problem, with_response_format));
+/// ```
+pub async fn with_response_format(
+    State(format): State<ResponseFormat>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let request_id = req
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|id| id.to_str().ok())
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let context = ResponseContext {
+        format,
+        instance: Some(req.uri().path().to_string()),
+        request_id: Some(request_id.clone()),
+    };
+    let mut res = RESPONSE_CONTEXT.scope(context, next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+        res.headers_mut().entry(X_REQUEST_ID).or_insert(value);
+    }
+    res
+}
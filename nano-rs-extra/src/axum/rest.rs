@@ -1,48 +1,237 @@
 use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 use axum::http::{header, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
 use bytes::{BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::axum::errors::ServerError;
+use crate::axum::problem::{self, Problem};
+use nano_rs_core::config::envelope::EnvelopeConfig;
+pub use nano_rs_core::config::rest::ResponseFormat;
+pub use nano_rs_core::config::rest::StatusPolicy;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RestResp<T> {
     pub code: i32,
     pub msg: String,
     pub data: Option<T>,
+    /// http status of the response, default 200, ignored under `StatusPolicy::AlwaysOk`
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+}
+
+static ALWAYS_OK: AtomicBool = AtomicBool::new(false);
+
+/// set how `RestResp` maps to http status, `AppStarter` sets it from `RestConfig.status_policy`
+pub fn set_status_policy(policy: StatusPolicy) {
+    ALWAYS_OK.store(policy == StatusPolicy::AlwaysOk, Ordering::Relaxed);
+}
+
+pub fn status_policy() -> StatusPolicy {
+    if ALWAYS_OK.load(Ordering::Relaxed) {
+        StatusPolicy::AlwaysOk
+    } else {
+        StatusPolicy::Http
+    }
+}
+
+/// resolved `RestResp` envelope shape, see `EnvelopeConfig`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope {
+    pub code_field: String,
+    pub msg_field: String,
+    pub data_field: String,
+    pub success_code: i32,
+    pub success_msg: String,
+    pub request_id_field: Option<String>,
+    pub timestamp_field: Option<String>,
+    pub extra: Map<String, Value>,
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Envelope {
+            code_field: "code".to_string(),
+            msg_field: "msg".to_string(),
+            data_field: "data".to_string(),
+            success_code: 200,
+            success_msg: "Success".to_string(),
+            request_id_field: None,
+            timestamp_field: None,
+            extra: Map::new(),
+        }
+    }
+}
+
+impl From<&EnvelopeConfig> for Envelope {
+    fn from(envelope_config: &EnvelopeConfig) -> Self {
+        let default = Envelope::default();
+        Envelope {
+            code_field: envelope_config.code_field.clone().unwrap_or(default.code_field),
+            msg_field: envelope_config.msg_field.clone().unwrap_or(default.msg_field),
+            data_field: envelope_config.data_field.clone().unwrap_or(default.data_field),
+            success_code: envelope_config.success_code.unwrap_or(default.success_code),
+            success_msg: envelope_config.success_msg.clone().unwrap_or(default.success_msg),
+            request_id_field: envelope_config.request_id_field.clone(),
+            timestamp_field: envelope_config.timestamp_field.clone(),
+            extra: envelope_config
+                .extra
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value)))
+                .collect(),
+        }
+    }
+}
+
+static ENVELOPE: RwLock<Option<Arc<Envelope>>> = RwLock::new(None);
+
+/// set `RestResp` envelope shape, `AppStarter` sets it from `RestConfig.envelope`
+pub fn set_envelope(envelope: Envelope) {
+    *ENVELOPE.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(envelope));
+}
+
+pub fn envelope() -> Arc<Envelope> {
+    ENVELOPE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// code of successful responses (`EnvelopeConfig.success_code`), used by `biz_ok!`
+pub fn success_code() -> i32 {
+    envelope().success_code
+}
+
+impl<T> RestResp<T> {
+    /// http status this response is sent with, according to the status policy
+    pub fn http_status(&self) -> StatusCode {
+        match status_policy() {
+            StatusPolicy::Http => self.status.unwrap_or(StatusCode::OK),
+            StatusPolicy::AlwaysOk => StatusCode::OK,
+        }
+    }
+
+    /// failed response, a 4xx/5xx status or a code that is neither the success code nor 2xx
+    pub fn is_error(&self) -> bool {
+        self.status
+            .is_some_and(|status| status.is_client_error() || status.is_server_error())
+            || (self.code != success_code() && !(200..300).contains(&self.code))
+    }
+
+    /// problem document of a failed response, `None` on success
+    ///
+    /// status is `status`, else the code when it is a http error status, else 400
+    pub fn to_problem(&self) -> Option<Problem> {
+        if !self.is_error() {
+            return None;
+        }
+        let status = self
+            .status
+            .filter(|status| status.is_client_error() || status.is_server_error())
+            .or_else(|| {
+                u16::try_from(self.code)
+                    .ok()
+                    .filter(|code| (400..600).contains(code))
+                    .and_then(|code| StatusCode::from_u16(code).ok())
+            })
+            .unwrap_or(StatusCode::BAD_REQUEST);
+        Some(Problem::new(status).detail(self.msg.as_str()).code(self.code))
+    }
+}
+
+impl<T> RestResp<T> where T: Serialize {
+    /// envelope json object with the configured field names and extra fields
+    pub fn to_envelope(&self, envelope: &Envelope) -> Result<Value, serde_json::Error> {
+        let mut body = Map::new();
+        body.insert(envelope.code_field.clone(), Value::from(self.code));
+        body.insert(envelope.msg_field.clone(), Value::from(self.msg.as_str()));
+        body.insert(envelope.data_field.clone(), serde_json::to_value(&self.data)?);
+        if let Some(field) = &envelope.request_id_field {
+            body.insert(field.clone(), problem::request_id().map(Value::from).unwrap_or(Value::Null));
+        }
+        if let Some(field) = &envelope.timestamp_field {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default();
+            body.insert(field.clone(), Value::from(timestamp));
+        }
+        for (key, value) in envelope.extra.iter() {
+            body.insert(key.clone(), value.clone());
+        }
+        Ok(Value::Object(body))
+    }
+
+    fn envelope_response(&self, status: StatusCode) -> Response {
+        let envelope = envelope();
+        if *envelope == Envelope::default() {
+            return json_response(status, mime::APPLICATION_JSON.as_ref(), self);
+        }
+        match self.to_envelope(&envelope) {
+            Ok(body) => json_response(status, mime::APPLICATION_JSON.as_ref(), &body),
+            Err(err) => json_response(StatusCode::INTERNAL_SERVER_ERROR, mime::TEXT_PLAIN_UTF_8.as_ref(), &err.to_string()),
+        }
+    }
 }
 
 impl<T> IntoResponse for RestResp<T> where T: Serialize {
     fn into_response(self) -> Response {
-        // Use a small initial capacity of 128 bytes like serde_json::to_vec
-        // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
-        let mut buf = BytesMut::with_capacity(128).writer();
-        match serde_json::to_writer(&mut buf, &self) {
-            Ok(()) => (
-                [(
-                    header::CONTENT_TYPE,
-                    HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
-                )],
-                buf.into_inner().freeze(),
-            )
-                .into_response(),
-            Err(err) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                [(
-                    header::CONTENT_TYPE,
-                    HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref()),
-                )],
-                err.to_string(),
-            )
-                .into_response(),
+        let format = problem::response_format();
+        if format == ResponseFormat::Envelope {
+            return self.envelope_response(self.http_status());
+        }
+        if let Some(problem) = self.to_problem() {
+            return problem.into_response();
+        }
+        let status = self.status.unwrap_or(StatusCode::OK);
+        if format == ResponseFormat::ProblemUnwrapped {
+            json_response(status, mime::APPLICATION_JSON.as_ref(), &self.data)
+        } else {
+            self.envelope_response(status)
         }
     }
 }
 
+/// serialize body as json response with content type
+pub(crate) fn json_response<B: Serialize>(status: StatusCode, content_type: &'static str, body: &B) -> Response {
+    // Use a small initial capacity of 128 bytes like serde_json::to_vec
+    // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
+    let mut buf = BytesMut::with_capacity(128).writer();
+    match serde_json::to_writer(&mut buf, body) {
+        Ok(()) => (
+            status,
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(content_type),
+            )],
+            buf.into_inner().freeze(),
+        )
+            .into_response(),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref()),
+            )],
+            err.to_string(),
+        )
+            .into_response(),
+    }
+}
+
 
 ///通用的返回错误的方法
 /// Common return error method
+///
+/// code that is a http status (e.g. 404, 500) is also sent as http status,
+/// use `biz_err_with_status` for business codes
 /// # Example
 /// ```rust
 ///
@@ -56,11 +245,32 @@ impl<T> IntoResponse for RestResp<T> where T: Serialize {
 /// }
 /// ```
 pub fn biz_err<T, S>(code: i32, msg: S) -> Result<RestResp<T>, ServerError> where S: ToString + Display + std::fmt::Debug {
+    let status = u16::try_from(code)
+        .ok()
+        .filter(|code| (400..600).contains(code))
+        .and_then(|code| StatusCode::from_u16(code).ok());
+    biz_err_with_status(status.unwrap_or(StatusCode::OK), code, msg)
+}
+
+///返回指定 http 状态码的错误
+/// Common return error method with http status
+/// # Example
+/// ```rust
+/// use axum::http::StatusCode;
+/// use nano_rs_extra::axum::errors::ServerError;
+/// use nano_rs_extra::axum::rest::{biz_err_with_status, RestResp};
+///
+/// pub async fn test() -> Result<RestResp<()>, ServerError> {
+///    biz_err_with_status(StatusCode::NOT_FOUND, 40001, "pet not found")
+/// }
+/// ```
+pub fn biz_err_with_status<T, S>(status: StatusCode, code: i32, msg: S) -> Result<RestResp<T>, ServerError> where S: ToString + Display + std::fmt::Debug {
     tracing::error!("code: {} - {:#?}",code,msg);
     Ok(RestResp {
         code,
         msg: msg.to_string(),
         data: None,
+        status: Some(status),
     })
 }
 
@@ -87,8 +297,9 @@ pub fn biz_err<T, S>(code: i32, msg: S) -> Result<RestResp<T>, ServerError> wher
 pub fn biz_ok<T>(code: i32, data: T) -> Result<RestResp<T>, ServerError> {
     Ok(RestResp {
         code,
-        msg: "Success".to_string(),
+        msg: envelope().success_msg.clone(),
         data: Some(data),
+        status: None,
     })
 }
 
@@ -110,7 +321,7 @@ pub fn biz_ok<T>(code: i32, data: T) -> Result<RestResp<T>, ServerError> {
 #[macro_export]
 macro_rules! biz_ok {
     ($data:expr) => {
-        nano_rs::axum::rest::biz_ok(200, $data)
+        nano_rs::axum::rest::biz_ok(nano_rs::axum::rest::success_code(), $data)
     };
 
     ($code:expr, $data:expr) => {{
@@ -118,7 +329,7 @@ macro_rules! biz_ok {
     }};
 
     () => {
-        nano_rs::axum::rest::biz_ok(200, ())
+        nano_rs::axum::rest::biz_ok(nano_rs::axum::rest::success_code(), ())
     };
 }
 
@@ -134,6 +345,8 @@ macro_rules! biz_ok {
 ///     let result: Result<RestResp<()>, ServerError> = biz_err!();
 ///     let result_with_msg: Result<RestResp<()>, ServerError> = biz_err!("Custom message");
 ///     let custom_result: Result<RestResp<()>, ServerError> = biz_err!(404, "Not Found");
+///     let with_status: Result<RestResp<()>, ServerError> =
+///         biz_err!(axum::http::StatusCode::NOT_FOUND, 40001, "pet not found");
 /// }
 /// ```
 ///
@@ -147,6 +360,10 @@ macro_rules! biz_err {
         nano_rs::axum::rest::biz_err($code, $msg)
     }};
 
+    ($status:expr, $code:expr, $msg:expr) => {{
+        nano_rs::axum::rest::biz_err_with_status($status, $code, $msg)
+    }};
+
     () => {
         nano_rs::axum::rest::biz_err(500, "error".to_string())
     };
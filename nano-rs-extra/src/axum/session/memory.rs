@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::axum::session::{SessionData, SessionFuture, SessionStore};
+
+/// sessions kept by `MemoryStore` before expired ones are pruned
+const MEMORY_STORE_PRUNE_SIZE: usize = 10_000;
+
+/// in-process sessions, lost on restart and not shared by instances
+#[derive(Default)]
+pub struct MemoryStore {
+    sessions: Mutex<HashMap<String, (SessionData, Instant)>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        MemoryStore::default()
+    }
+}
+
+impl SessionStore for MemoryStore {
+    fn load<'a>(&'a self, id: &'a str) -> SessionFuture<'a, Option<SessionData>> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let data = match sessions.get(id) {
+            Some((data, expire_at)) if *expire_at > Instant::now() => Some(data.clone()),
+            Some(_) => {
+                sessions.remove(id);
+                None
+            }
+            None => None,
+        };
+        Box::pin(std::future::ready(Ok(data)))
+    }
+
+    fn save<'a>(&'a self, id: &'a str, data: &'a SessionData, ttl: Duration) -> SessionFuture<'a, ()> {
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if sessions.len() >= MEMORY_STORE_PRUNE_SIZE && !sessions.contains_key(id) {
+            sessions.retain(|_, (_, expire_at)| *expire_at > now);
+        }
+        sessions.insert(id.to_string(), (data.clone(), now + ttl));
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> SessionFuture<'a, ()> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use axum::http::{HeaderMap, HeaderName};
+use nano_rs_core::config::session::SessionConfig;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::axum::cookie::{get_cookie, random_token, set_cookie, Cookie, CookieKey, SameSite};
+
+pub mod memory;
+#[cfg(feature = "redis")]
+pub mod redis;
+
+pub use memory::MemoryStore;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisStore;
+
+/// session key of the claims set by `Session::login`
+pub const CLAIMS_KEY: &str = "_claims";
+
+/// session key of the csrf token
+pub const CSRF_KEY: &str = "_csrf";
+
+pub type SessionData = HashMap<String, Value>;
+
+/// boxed future returned by [`SessionStore`]
+pub type SessionFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+/// 会话存储
+/// Session storage, `MemoryStore` in process, `RedisStore` (feature `redis`) shared by instances
+pub trait SessionStore: Send + Sync {
+    fn load<'a>(&'a self, id: &'a str) -> SessionFuture<'a, Option<SessionData>>;
+
+    /// save and reset the time to live
+    fn save<'a>(&'a self, id: &'a str, data: &'a SessionData, ttl: Duration) -> SessionFuture<'a, ()>;
+
+    fn delete<'a>(&'a self, id: &'a str) -> SessionFuture<'a, ()>;
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    id: Option<String>,
+    data: SessionData,
+    changed: bool,
+    renew: bool,
+    destroyed: bool,
+    login: bool,
+}
+
+/// 会话
+/// Session of the request, loaded by the session layer and saved after the handler when changed
+///
+/// # Example
+/// ```rust
+/// use nano_rs_extra::axum::errors::ServerError;
+/// use nano_rs_extra::axum::rest::{biz_ok, RestResp};
+/// use nano_rs_extra::axum::session::Session;
+/// use serde_json::json;
+///
+/// // send it back in the `x-csrf-token` header of POST, PUT, PATCH and DELETE requests
+/// pub async fn csrf(session: Session) -> Result<RestResp<String>, ServerError> {
+///     biz_ok(200, session.csrf_token())
+/// }
+///
+/// pub async fn login(session: Session) -> Result<RestResp<()>, ServerError> {
+///     // check the password things...
+///     session.login(&json!({"sub": "mantou", "roles": ["admin"]}))?;
+///     biz_ok(200, ())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    state: Arc<Mutex<SessionState>>,
+}
+
+impl Session {
+    fn new(id: Option<String>, data: SessionData) -> Self {
+        Session {
+            state: Arc::new(Mutex::new(SessionState {
+                id,
+                data,
+                ..Default::default()
+            })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SessionState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// none until the session is saved
+    pub fn id(&self) -> Option<String> {
+        self.lock().id.clone()
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.lock().data.get(key).cloned()?;
+        serde_json::from_value(value).ok()
+    }
+
+    pub fn insert<T: Serialize>(&self, key: &str, value: &T) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        let mut state = self.lock();
+        state.data.insert(key.to_string(), value);
+        state.changed = true;
+        Ok(())
+    }
+
+    pub fn remove(&self, key: &str) -> Option<Value> {
+        let mut state = self.lock();
+        let value = state.data.remove(key);
+        state.changed |= value.is_some();
+        value
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.data.clear();
+        state.changed = true;
+    }
+
+    /// move the data to a new session id, call it when the privilege changes (login etc..)
+    pub fn renew(&self) {
+        let mut state = self.lock();
+        state.renew = true;
+        state.changed = true;
+    }
+
+    /// delete the session and expire the cookie
+    pub fn destroy(&self) {
+        let mut state = self.lock();
+        state.data.clear();
+        state.destroyed = true;
+    }
+
+    /// set the claims of `require_auth` and `Claims`, renews the session id
+    ///
+    /// with the csrf check on, the request must carry the `csrf_token` issued before the login,
+    /// else the session layer drops the login and responds 403 (login csrf)
+    pub fn login<T: Serialize>(&self, claims: &T) -> Result<(), serde_json::Error> {
+        self.insert(CLAIMS_KEY, claims)?;
+        self.renew();
+        self.lock().login = true;
+        Ok(())
+    }
+
+    /// `login` was called in this request
+    pub fn is_login(&self) -> bool {
+        self.lock().login
+    }
+
+    pub fn logout(&self) {
+        self.destroy();
+    }
+
+    /// claims set by `login`
+    pub fn claims(&self) -> Option<Value> {
+        self.lock().data.get(CLAIMS_KEY).cloned()
+    }
+
+    /// csrf token of the session, created on first use
+    pub fn csrf_token(&self) -> String {
+        let mut state = self.lock();
+        if let Some(Value::String(token)) = state.data.get(CSRF_KEY) {
+            return token.clone();
+        }
+        let token = random_token();
+        state.data.insert(CSRF_KEY.to_string(), Value::String(token.clone()));
+        state.changed = true;
+        token
+    }
+
+    /// constant time check of a csrf token against the session one
+    pub fn verify_csrf(&self, token: &str) -> bool {
+        match self.lock().data.get(CSRF_KEY) {
+            Some(Value::String(expected)) => {
+                expected.len() == token.len()
+                    && expected
+                        .bytes()
+                        .zip(token.bytes())
+                        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                        == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 会话管理
+/// Load the session of the signed id cookie and save it back, used by the session layer
+pub struct SessionManager {
+    pub store: Arc<dyn SessionStore>,
+    pub key: CookieKey,
+    /// name and attributes of the session cookie, its `max_age` is also the session ttl
+    pub cookie: Cookie,
+    /// header of the csrf token, none disables the csrf check
+    pub csrf_header: Option<HeaderName>,
+}
+
+impl SessionManager {
+    pub fn new(secret: &[u8], store: Arc<dyn SessionStore>) -> Self {
+        SessionManager {
+            store,
+            key: CookieKey::from_secret(secret),
+            cookie: Cookie::new("nano_session", "")
+                .path("/")
+                .max_age(86400)
+                .secure(true)
+                .http_only(true)
+                .same_site(SameSite::Lax),
+            csrf_header: Some(HeaderName::from_static("x-csrf-token")),
+        }
+    }
+
+    pub fn from_config(session_config: &SessionConfig) -> Result<Self, String> {
+        let secret = session_config
+            .secret
+            .clone()
+            .ok_or("session secret is required".to_string())?;
+        let store: Arc<dyn SessionStore> = match session_config.get_store().as_str() {
+            "memory" => Arc::new(MemoryStore::new()),
+            #[cfg(feature = "redis")]
+            "redis" => Arc::new(RedisStore::from_config(
+                &session_config.redis.clone().ok_or("redis of the redis session store is required".to_string())?,
+            )?),
+            #[cfg(not(feature = "redis"))]
+            "redis" => return Err("redis session store needs the redis feature".to_string()),
+            store => return Err(format!("unsupported session store: {}", store)),
+        };
+        let mut cookie = Cookie::new(session_config.get_cookie_name().as_str(), "")
+            .path(session_config.get_path().as_str())
+            .max_age(session_config.get_max_age())
+            .secure(session_config.is_secure())
+            .http_only(session_config.is_http_only())
+            .same_site(session_config.get_same_site().parse()?);
+        if let Some(domain) = session_config.domain.as_ref() {
+            cookie = cookie.domain(domain);
+        }
+        let csrf_header = if session_config.is_csrf() {
+            Some(
+                HeaderName::try_from(session_config.get_csrf_header())
+                    .map_err(|e| format!("invalid csrf header: {}", e))?,
+            )
+        } else {
+            None
+        };
+        Ok(SessionManager {
+            store,
+            key: CookieKey::from_secret(secret.as_bytes()),
+            cookie,
+            csrf_header,
+        })
+    }
+
+    pub fn cookie(mut self, cookie: Cookie) -> Self {
+        self.cookie = cookie;
+        self
+    }
+
+    pub fn csrf_header(mut self, csrf_header: Option<HeaderName>) -> Self {
+        self.csrf_header = csrf_header;
+        self
+    }
+
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(self.cookie.max_age.unwrap_or(86400))
+    }
+
+    /// session of the request cookie, a new empty one when it is missing, forged or expired
+    pub async fn load(&self, headers: &HeaderMap) -> Session {
+        let Some(id) = get_cookie(headers, self.cookie.name.as_str())
+            .and_then(|value| self.key.verify(self.cookie.name.as_str(), value.as_str()))
+        else {
+            return Session::default();
+        };
+        match self.store.load(id.as_str()).await {
+            Ok(Some(data)) => Session::new(Some(id), data),
+            Ok(None) => Session::default(),
+            Err(err) => {
+                tracing::warn!("load session failed: {}", err);
+                Session::default()
+            }
+        }
+    }
+
+    /// save the changed session and set the cookie of a new id on the response
+    pub async fn commit(&self, session: &Session, headers: &mut HeaderMap) {
+        let (id, data, renew, destroyed) = {
+            let mut state = session.lock();
+            if !state.changed && !state.destroyed {
+                return;
+            }
+            state.changed = false;
+            (
+                state.id.clone(),
+                state.data.clone(),
+                std::mem::take(&mut state.renew),
+                state.destroyed,
+            )
+        };
+        if destroyed || renew {
+            if let Some(id) = id.as_ref() {
+                if let Err(err) = self.store.delete(id).await {
+                    tracing::warn!("delete session failed: {}", err);
+                }
+            }
+        }
+        if destroyed {
+            if id.is_some() {
+                let mut removal = self.cookie.clone();
+                removal.max_age = Some(0);
+                set_cookie(headers, &removal);
+            }
+            return;
+        }
+        let new_id = match id {
+            Some(id) if !renew => id,
+            _ => random_token(),
+        };
+        if let Err(err) = self.store.save(new_id.as_str(), &data, self.ttl()).await {
+            tracing::warn!("save session failed: {}", err);
+            return;
+        }
+        session.lock().id = Some(new_id.clone());
+        let mut cookie = self.cookie.clone();
+        cookie.value = self.key.sign(self.cookie.name.as_str(), new_id.as_str());
+        set_cookie(headers, &cookie);
+    }
+
+    /// csrf check of unsafe methods, sessions without a saved id have nothing to forge
+    ///
+    /// a `Session::login` in such a request still needs the token, see `has_csrf_token`
+    pub fn check_csrf(&self, session: &Session, headers: &HeaderMap) -> bool {
+        self.csrf_header.is_none() || session.id().is_none() || self.has_csrf_token(session, headers)
+    }
+
+    /// the csrf header carries the token of the session, true when the check is off
+    pub fn has_csrf_token(&self, session: &Session, headers: &HeaderMap) -> bool {
+        let Some(csrf_header) = self.csrf_header.as_ref() else {
+            return true;
+        };
+        headers
+            .get(csrf_header)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|token| session.verify_csrf(token))
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use nano_rs_core::config::redis::RedisConfig;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use tokio::sync::OnceCell;
+
+use crate::axum::session::{SessionData, SessionFuture, SessionStore};
+
+/// sessions in redis as json with `SET EX`, shared by every instance
+///
+/// the connection is opened on first use, redis cluster is not supported
+pub struct RedisStore {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+    prefix: String,
+}
+
+impl RedisStore {
+    pub fn new(client: redis::Client) -> Self {
+        RedisStore {
+            client,
+            connection: OnceCell::new(),
+            prefix: "nano:session:".to_string(),
+        }
+    }
+
+    pub fn from_config(redis_config: &RedisConfig) -> Result<Self, String> {
+        if redis_config.cluster.as_ref().is_some_and(|cluster| !cluster.is_empty()) {
+            return Err("redis cluster is not supported by the session store".to_string());
+        }
+        let client = redis::Client::open(redis_config.node.get_node_url())
+            .map_err(|e| format!("open redis of session store failed: {}", e))?;
+        Ok(RedisStore::new(client))
+    }
+
+    /// key prefix, default "nano:session:"
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    fn key(&self, id: &str) -> String {
+        format!("{}{}", self.prefix, id)
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, String> {
+        self.connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+            .map_err(|e| format!("connect redis failed: {}", e))
+    }
+}
+
+impl SessionStore for RedisStore {
+    fn load<'a>(&'a self, id: &'a str) -> SessionFuture<'a, Option<SessionData>> {
+        Box::pin(async move {
+            let mut connection = self.connection().await?;
+            let value: Option<String> = connection
+                .get(self.key(id))
+                .await
+                .map_err(|e| format!("redis GET failed: {}", e))?;
+            value
+                .map(|value| serde_json::from_str(value.as_str()))
+                .transpose()
+                .map_err(|e| format!("invalid session data: {}", e))
+        })
+    }
+
+    fn save<'a>(&'a self, id: &'a str, data: &'a SessionData, ttl: Duration) -> SessionFuture<'a, ()> {
+        Box::pin(async move {
+            let value = serde_json::to_string(data).map_err(|e| e.to_string())?;
+            let mut connection = self.connection().await?;
+            connection
+                .set_ex::<_, _, ()>(self.key(id), value, ttl.as_secs().max(1))
+                .await
+                .map_err(|e| format!("redis SET failed: {}", e))
+        })
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> SessionFuture<'a, ()> {
+        Box::pin(async move {
+            let mut connection = self.connection().await?;
+            connection
+                .del::<_, ()>(self.key(id))
+                .await
+                .map_err(|e| format!("redis DEL failed: {}", e))
+        })
+    }
+}
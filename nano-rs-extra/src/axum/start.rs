@@ -1,35 +1,104 @@
 use std::convert::Infallible;
+use std::future::IntoFuture;
 use std::sync::Arc;
 
+use axum::error_handling::HandleErrorLayer;
 use axum::extract::Request;
 use axum::response::IntoResponse;
-use axum::routing::Route;
+use axum::handler::Handler;
+use axum::routing::{any, MethodRouter, Route};
 use axum::Router;
 use axum_client_ip;
 use axum_client_ip::ClientIpSource;
+use nano_rs_core::config::compression::CompressionConfig;
+use nano_rs_core::config::cors::CorsConfig;
 use nano_rs_core::config::logger::LogConfig;
 use nano_rs_core::config::rest::RestConfig;
-use tower::{Layer, Service};
-use tower_http::cors::{Any, CorsLayer};
+use nano_rs_core::config::security_headers::SecurityHeadersConfig;
+use nano_rs_core::config::static_files::StaticConfig;
+use tower::limit::GlobalConcurrencyLimitLayer;
+use tower::{Layer, Service, ServiceBuilder};
 
+use crate::axum::generator::gen_api_info::ApiInfo;
+use crate::axum::health::HealthChecks;
+use crate::axum::middleware::ip_filter::IpFilter;
+use crate::axum::middleware::rate_limit::RateLimiter;
 use crate::axum::shutdown::shutdown_signal;
-use crate::axum::{handler, middleware};
+use crate::axum::static_files::StaticFiles;
+use crate::axum::rest::ResponseFormat;
+use crate::axum::session::SessionManager;
+use crate::axum::{admin, handler, i18n, middleware, page, problem, rest};
 
 /// AppStarter
 pub struct AppStarter {
     pub app: Router,
     pub rest_config: Arc<RestConfig>,
+    /// operational routes served by the admin listener (see `RestConfig.admin`)
+    pub admin_app: Router,
+    /// fallback for unmatched paths, default `handler_404`
+    pub fallback: MethodRouter,
+    /// fallback for unsupported methods of existing paths, default `handler_405`
+    pub method_not_allowed_fallback: MethodRouter,
+    /// set by `add_secure_client_ip_source_layer`, re-applied outside the config layers
+    pub client_ip_source: Option<ClientIpSource>,
 }
 
 impl AppStarter {
     /// new starter with axum app and rest config
     pub fn new(app: Router, rest_config: RestConfig) -> Self {
+        rest::set_status_policy(rest_config.status_policy.unwrap_or_default());
+        problem::set_response_format(rest_config.response_format.unwrap_or_default());
+        if let Some(envelope_config) = &rest_config.envelope {
+            rest::set_envelope(rest::Envelope::from(envelope_config));
+        }
+        if let Some(page_config) = &rest_config.page {
+            page::set_page_config(page_config);
+        }
+        match rest_config.auth.as_ref() {
+            Some(auth_config) if auth_config.is_enable() => middleware::auth::set_jwt_verifier(
+                middleware::auth::JwtVerifier::from_config(auth_config).unwrap_or_else(|e| panic!("{}", e)),
+            ),
+            Some(_) => middleware::auth::disable_auth(),
+            None => {}
+        }
+        if let Some(i18n_config) = rest_config.i18n.as_ref().filter(|c| c.is_enable()) {
+            i18n::set_i18n(i18n::I18n::from_config(i18n_config).unwrap_or_else(|e| panic!("{}", e)));
+        }
         AppStarter {
             app,
             rest_config: Arc::new(rest_config),
+            admin_app: Router::new(),
+            fallback: any(handler::not_page::handler_404),
+            method_not_allowed_fallback: any(handler::not_page::handler_405),
+            client_ip_source: None,
         }
     }
 
+    /// add business routes to axum app, nested under `base_path` of rest config
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::Router;
+    /// use nano_rs_core::config::rest::RestConfig;
+    /// use nano_rs_extra::axum::start::AppStarter;
+    ///
+    /// let rest_config = RestConfig {
+    ///     base_path: "/v1".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// // routes generated by AxumGenRoute, e.g. get_routes(service_context, rest_config)
+    /// let routes = Router::new();
+    /// let starter = AppStarter::new(Router::new(), rest_config).with_routes(routes);
+    /// ```
+    pub fn with_routes(mut self, routes: Router) -> Self {
+        let routes = routes.method_not_allowed_fallback(self.method_not_allowed_fallback.clone());
+        self.app = match self.rest_config.get_base_path() {
+            None => self.app.merge(routes),
+            Some(base_path) => self.app.nest(base_path.as_str(), routes),
+        };
+        self
+    }
+
     /// easy run axum server with rest config
     ///
     /// # Example
@@ -60,28 +129,44 @@ impl AppStarter {
     /// }
     /// ```
     pub async fn run(self) {
-        let host = self
+        let starter = self.apply_config_layers();
+        let host = starter
             .rest_config
             .host
             .clone()
             .unwrap_or_else(|| "127.0.0.1".to_string());
-        let port = self.rest_config.port.clone().to_string();
+        let port = starter.rest_config.port.clone().to_string();
         let address = format!("{}:{}", host, port);
         let listener = tokio::net::TcpListener::bind(address).await.unwrap();
-        let url = format!("http://{}", listener.local_addr().unwrap());
-        let link = format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, url);
-        tracing::info!("listening on {}", link);
-        axum::serve(
+        print_listening("listening", &listener);
+        let public = axum::serve(
             listener,
-            self.app
+            starter.app
                 .into_make_service_with_connect_info::<std::net::SocketAddr>(),
         )
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .unwrap();
+        .with_graceful_shutdown(shutdown_signal());
+        match starter.rest_config.get_admin_config() {
+            None => public.await.unwrap(),
+            Some(admin_config) => {
+                let admin_listener = tokio::net::TcpListener::bind(admin_config.get_address())
+                    .await
+                    .unwrap();
+                print_listening("admin listening", &admin_listener);
+                let admin_app =
+                    admin::admin_routes(starter.rest_config.clone()).merge(starter.admin_app);
+                let admin = axum::serve(
+                    admin_listener,
+                    admin_app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+                )
+                .with_graceful_shutdown(shutdown_signal());
+                let (public, admin) = tokio::join!(public.into_future(), admin.into_future());
+                public.unwrap();
+                admin.unwrap();
+            }
+        }
     }
 
-    /// run service with dev mode(all cors allowed)
+    /// run service with dev mode(all cors allowed unless `cors` is configured)
     /// # Example
     /// ```rust
     /// use axum::Router;
@@ -106,11 +191,117 @@ impl AppStarter {
     /// }
     /// ```
     pub async fn run_dev(self) {
-        self.add_log_layer_with_config(None)
-            .add_secure_client_ip_source_layer(ClientIpSource::ConnectInfo)
-            .add_dev_cors_layer()
-            .run()
-            .await;
+        let starter = self
+            .add_log_layer_with_config(None)
+            .add_secure_client_ip_source_layer(ClientIpSource::ConnectInfo);
+        if starter.rest_config.cors.is_none() {
+            starter.add_dev_cors_layer().run().await;
+        } else {
+            starter.run().await;
+        }
+    }
+
+    /// layers driven by rest config, applied as the outermost layers when the server starts
+    fn apply_config_layers(mut self) -> Self {
+        self.app = self
+            .app
+            .fallback(self.fallback.clone())
+            .method_not_allowed_fallback(self.method_not_allowed_fallback.clone());
+        if let Some(rate_limit_config) = self.rest_config.rate_limit.clone() {
+            if rate_limit_config.is_enable() && self.app.has_routes() {
+                self = self.add_rate_limit_layer(RateLimiter::from_config(&rate_limit_config));
+            }
+        }
+        if let Some(static_files) = self.rest_config.static_files.clone() {
+            for static_config in static_files {
+                self = self.add_static_files(static_config);
+            }
+        }
+        if let Some(ip_filter_config) = self.rest_config.ip_filter.clone() {
+            if ip_filter_config.is_enable() {
+                self = self.add_ip_filter_layer(IpFilter::from_config(&ip_filter_config));
+            }
+        }
+        if self.rest_config.panic_recovery.unwrap_or(true) {
+            self = self.add_panic_recovery_layer();
+        }
+        if let Some(concurrency_config) = self.rest_config.concurrency.clone() {
+            if concurrency_config.is_enable() {
+                self = self.add_concurrency_limit_layer(
+                    concurrency_config.max_concurrent,
+                    concurrency_config.is_load_shed(),
+                );
+            }
+        }
+        if let Some(compression_config) = self.rest_config.compression.clone() {
+            if compression_config.is_enable() {
+                self = self.add_compression_layer(compression_config);
+            }
+        }
+        if let Some(security_headers_config) = self.rest_config.security_headers.clone() {
+            if security_headers_config.is_enable() {
+                self = self.add_security_headers_layer(security_headers_config);
+            }
+        }
+        if let Some(session_config) = self.rest_config.session.clone() {
+            if session_config.is_enable() {
+                self = self.add_session_layer(
+                    SessionManager::from_config(&session_config).unwrap_or_else(|e| panic!("{}", e)),
+                );
+            }
+        }
+        let format = self.rest_config.response_format.unwrap_or_default();
+        // the request context also carries the request id of the envelope
+        let request_id = self
+            .rest_config
+            .envelope
+            .as_ref()
+            .is_some_and(|envelope_config| envelope_config.request_id_field.is_some());
+        if format != ResponseFormat::Envelope || request_id {
+            self = self.add_response_format_layer(format);
+        }
+        if self.rest_config.i18n.as_ref().is_some_and(|c| c.is_enable()) {
+            self = self.add_locale_layer();
+        }
+        if let Some(cors_config) = self.rest_config.cors.clone() {
+            self = self.add_cors_layer(cors_config);
+        }
+        // rate limit and ip filter above run outside the layer added by the user
+        if let Some(client_ip_source) = self.client_ip_source.clone() {
+            self.app = self.app.layer(client_ip_source.into_extension());
+        }
+        self
+    }
+
+    /// replace the 404 fallback (json `RestResp` by default)
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::StatusCode;
+    /// use axum::Router;
+    /// use nano_rs_core::config::rest::RestConfig;
+    /// use nano_rs_extra::axum::start::AppStarter;
+    ///
+    /// let starter = AppStarter::new(Router::new(), RestConfig::default())
+    ///     .set_fallback(|| async { (StatusCode::NOT_FOUND, "nothing here") });
+    /// ```
+    pub fn set_fallback<H, T>(mut self, handler: H) -> Self
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        self.fallback = any(handler);
+        self
+    }
+
+    /// replace the 405 fallback (json `RestResp` by default), call it before `with_routes`
+    pub fn set_method_not_allowed_fallback<H, T>(mut self, handler: H) -> Self
+    where
+        H: Handler<T, ()>,
+        T: 'static,
+    {
+        self.method_not_allowed_fallback = any(handler);
+        self
     }
 
     /// add log layer to axum app
@@ -129,7 +320,7 @@ impl AppStarter {
             .clone()
             .unwrap_or(false);
         let log_req = self.rest_config.log.log_req.clone().unwrap_or(true);
-        let app = self.app.clone().fallback(handler::not_page::handler_404);
+        let app = self.app.clone();
         self.app = if log_req {
             if log_request_body {
                 if log_response_body {
@@ -165,7 +356,7 @@ impl AppStarter {
             .clone()
             .unwrap_or(false);
         let log_req = self.rest_config.log.log_req.clone().unwrap_or(true);
-        let app = self.app.clone().fallback(handler::not_page::handler_404);
+        let app = self.app.clone();
         self.app = if log_req {
             if log_request_body {
                 if log_response_body {
@@ -212,18 +403,273 @@ impl AppStarter {
 
     /// add secure client ip source layer to axum app
     pub fn add_secure_client_ip_source_layer(mut self, sci: ClientIpSource) -> Self {
+        self.client_ip_source = Some(sci.clone());
         self.app = self.app.layer(sci.into_extension());
         self
     }
 
     /// add all allowed cors layer to axum app(dev mode)
-    pub fn add_dev_cors_layer(mut self) -> Self {
-        self.app = self.app.layer(
-            CorsLayer::new()
-                .allow_headers(Any)
-                .allow_origin(Any)
-                .allow_methods(Any),
-        );
+    pub fn add_dev_cors_layer(self) -> Self {
+        self.add_cors_layer(CorsConfig::permissive())
+    }
+
+    /// add cors layer with cors config to axum app
+    ///
+    /// `cors` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_cors_layer(mut self, cors_config: CorsConfig) -> Self {
+        self.app = self
+            .app
+            .layer(middleware::cors::cors_layer(&cors_config));
+        self
+    }
+
+    /// add `/healthz` (liveness) and `/readyz` (readiness) routes to axum app
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::Router;
+    /// use nano_rs_core::config::rest::RestConfig;
+    /// use nano_rs_extra::axum::health::HealthChecks;
+    /// use nano_rs_extra::axum::start::AppStarter;
+    ///
+    /// let starter = AppStarter::new(Router::new(), RestConfig::default()).add_health_routes(
+    ///     HealthChecks::new().add_check_fn("redis", || async {
+    ///         // redis PING things...
+    ///         Ok(())
+    ///     }),
+    /// );
+    /// ```
+    pub fn add_health_routes(self, checks: HealthChecks) -> Self {
+        self.add_operational_routes(checks.into_router())
+    }
+
+    /// add prometheus http metrics layer to axum app, `/metrics` is served by the admin listener
+    /// when `admin` is configured, otherwise by the app itself
+    pub fn add_prometheus_layer(mut self) -> Self {
+        let enable = self
+            .rest_config
+            .prometheus
+            .clone()
+            .unwrap_or_default()
+            .enable
+            .unwrap_or(true);
+        if !enable {
+            return self;
+        }
+        self.app = self.app.route_layer(axum::middleware::from_fn(
+            middleware::prometheus::track_metrics,
+        ));
+        if self.rest_config.get_admin_config().is_none() {
+            self.app = self.app.route(
+                admin::METRICS_PATH,
+                axum::routing::get(middleware::prometheus::metrics_handler),
+            );
+        }
+        self
+    }
+
+    /// add `ApiInfo` route listing (`/routes`) to the admin listener,
+    /// `base_path` of rest config is prepended to every `ApiInfo.base_path`
+    pub fn add_api_info(mut self, api_info: Vec<ApiInfo>) -> Self {
+        let api_info = match self.rest_config.get_base_path() {
+            None => api_info,
+            Some(base_path) => api_info
+                .into_iter()
+                .map(|info| info.with_base_path(base_path.as_str()))
+                .collect(),
+        };
+        self.admin_app = self.admin_app.merge(admin::api_info_routes(api_info));
+        self
+    }
+
+    /// add routes to the admin listener
+    pub fn add_admin_routes(mut self, routes: Router) -> Self {
+        self.admin_app = self.admin_app.merge(routes);
+        self
+    }
+
+    /// operational routes go to the admin listener when it is enabled, otherwise to the app
+    fn add_operational_routes(mut self, routes: Router) -> Self {
+        if self.rest_config.get_admin_config().is_some() {
+            self.admin_app = self.admin_app.merge(routes);
+        } else {
+            self.app = self.app.merge(routes);
+        }
+        self
+    }
+
+    /// serve a static directory (or SPA) under `path`, a mount at "/" takes over the fallback
+    /// but paths under `base_path` still get the json 404
+    ///
+    /// `static_files` in rest config are mounted by `run` automatically (after the log layers,
+    /// so static assets are not traced), no need to call this for them
+    pub fn add_static_files(mut self, static_config: StaticConfig) -> Self {
+        let path = static_config.get_path();
+        let static_files = StaticFiles::new(&static_config, self.fallback.clone());
+        tracing::debug!("serve static dir {} on {}", static_config.dir, path);
+        if path == "/" {
+            let static_files = static_files.api_path(self.rest_config.get_base_path());
+            self.app = self.app.fallback_service(static_files.into_method_router());
+        } else {
+            self.app = self
+                .app
+                .nest_service(path.as_str(), static_files.into_method_router());
+        }
+        self
+    }
+
+    /// add panic recovery layer to axum app, a handler panic is logged with the request id
+    /// and answered with a 500 `RestResp`
+    ///
+    /// applied by `run` automatically unless `panic_recovery: false` in rest config
+    pub fn add_panic_recovery_layer(mut self) -> Self {
+        middleware::panic::install_panic_hook();
+        self.app = self
+            .app
+            .layer(axum::middleware::from_fn(middleware::panic::catch_panic));
+        self
+    }
+
+    /// add ip allow/deny layer to axum app, checked against `ClientIp` (see
+    /// `add_secure_client_ip_source_layer`), rejected requests get a 403 `RestResp`
+    ///
+    /// route rules match the full request path (including `base_path`)
+    ///
+    /// `ip_filter` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_ip_filter_layer(mut self, filter: IpFilter) -> Self {
+        self.app = self.app.layer(axum::middleware::from_fn_with_state(
+            Arc::new(filter),
+            middleware::ip_filter::ip_filter,
+        ));
+        self
+    }
+
+    /// add rate limit layer to every route of axum app, keyed by `ClientIp` by default
+    ///
+    /// `rate_limit` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_rate_limit_layer(mut self, limiter: RateLimiter) -> Self {
+        self.app = self.app.route_layer(axum::middleware::from_fn_with_state(
+            limiter,
+            middleware::rate_limit::rate_limit,
+        ));
+        self
+    }
+
+    /// limit in-flight requests of axum app to `max_concurrent`, with `load_shed` requests
+    /// over the limit get a 503 `RestResp` instead of waiting
+    ///
+    /// the limit is server wide, every route shares one semaphore
+    ///
+    /// `concurrency` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_concurrency_limit_layer(mut self, max_concurrent: usize, load_shed: bool) -> Self {
+        let limit = GlobalConcurrencyLimitLayer::new(max_concurrent.max(1));
+        if load_shed {
+            self.app = self.app.layer(
+                ServiceBuilder::new()
+                    .layer(HandleErrorLayer::new(middleware::load_shed::handle_overloaded))
+                    .load_shed()
+                    .layer(limit),
+            );
+        } else {
+            self.app = self.app.layer(limit);
+        }
+        self
+    }
+
+    /// add response compression layer to axum app, and request decompression unless
+    /// `decompress_request: false`
+    ///
+    /// `compression` in rest config is applied by `run` automatically, no need to call this for it
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::Router;
+    /// use nano_rs_core::config::compression::CompressionConfig;
+    /// use nano_rs_core::config::rest::RestConfig;
+    /// use nano_rs_extra::axum::start::AppStarter;
+    ///
+    /// let starter = AppStarter::new(Router::new(), RestConfig::default()).add_compression_layer(
+    ///     CompressionConfig {
+    ///         algorithms: Some(vec!["gzip".to_string(), "br".to_string()]),
+    ///         min_size: Some(512),
+    ///         exclude_content_types: Some(vec!["application/octet-stream".to_string()]),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// ```
+    pub fn add_compression_layer(mut self, compression_config: CompressionConfig) -> Self {
+        if compression_config.is_decompress_request() {
+            self.app = self
+                .app
+                .layer(middleware::compression::decompression_layer(&compression_config));
+        }
+        self.app = self
+            .app
+            .layer(middleware::compression::compression_layer(&compression_config));
+        self
+    }
+
+    /// add security headers layer to axum app (HSTS, `X-Content-Type-Options`, `X-Frame-Options`,
+    /// `Referrer-Policy`, CSP), `Server` style headers are stripped
+    ///
+    /// `security_headers` in rest config is applied by `run` automatically, no need to call this for it
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::Router;
+    /// use nano_rs_core::config::rest::RestConfig;
+    /// use nano_rs_core::config::security_headers::SecurityHeadersConfig;
+    /// use nano_rs_extra::axum::start::AppStarter;
+    ///
+    /// let starter = AppStarter::new(Router::new(), RestConfig::default()).add_security_headers_layer(
+    ///     SecurityHeadersConfig {
+    ///         frame_options: Some("SAMEORIGIN".to_string()),
+    ///         content_security_policy: Some("default-src 'self'".to_string()),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// ```
+    pub fn add_security_headers_layer(
+        mut self,
+        security_headers_config: SecurityHeadersConfig,
+    ) -> Self {
+        self.app = self.app.layer(axum::middleware::from_fn_with_state(
+            Arc::new(middleware::security_headers::SecurityHeaders::new(
+                &security_headers_config,
+            )),
+            middleware::security_headers::security_headers,
+        ));
+        self
+    }
+
+    /// answer errors of axum app with rfc 7807 `application/problem+json`
+    /// (see `ResponseFormat`), route groups can use their own `with_response_format` layer
+    ///
+    /// `response_format` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_response_format_layer(mut self, format: ResponseFormat) -> Self {
+        self.app = self.app.layer(axum::middleware::from_fn_with_state(
+            format,
+            problem::with_response_format,
+        ));
+        self
+    }
+
+    /// load the cookie `Session` of every request and check the csrf token of unsafe methods
+    ///
+    /// `session` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_session_layer(mut self, manager: SessionManager) -> Self {
+        self.app = self.app.layer(axum::middleware::from_fn_with_state(
+            Arc::new(manager),
+            middleware::session::session,
+        ));
+        self
+    }
+
+    /// pick the request locale of i18n messages from `Accept-Language` (see `i18n::I18n`)
+    ///
+    /// `i18n` in rest config is applied by `run` automatically, no need to call this for it
+    pub fn add_locale_layer(mut self) -> Self {
+        self.app = self.app.layer(axum::middleware::from_fn(i18n::with_locale));
         self
     }
 
@@ -248,3 +694,9 @@ impl AppStarter {
         self
     }
 }
+
+fn print_listening(name: &str, listener: &tokio::net::TcpListener) {
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let link = format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, url);
+    tracing::info!("{} on {}", name, link);
+}
@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use axum::extract::Request;
+use axum::http::{header, HeaderValue, Method};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{any, MethodRouter};
+use nano_rs_core::config::static_files::StaticConfig;
+use tower::ServiceExt;
+use tower_http::services::{ServeDir, ServeFile};
+
+use crate::axum::handler::not_page::accept_html;
+
+/// 静态文件服务
+/// Static file (and SPA) service of a `StaticConfig` mount
+///
+/// missing files go to the SPA index (html navigations only) or to the app fallback,
+/// so api clients keep getting the json 404
+///
+/// # Example
+/// ```rust
+/// use axum::routing::any;
+/// use axum::Router;
+/// use nano_rs_core::config::static_files::StaticConfig;
+/// use nano_rs_extra::axum::handler::not_page::handler_404;
+/// use nano_rs_extra::axum::static_files::StaticFiles;
+///
+/// let static_config = StaticConfig {
+///     path: "/admin".to_string(),
+///     dir: "web/dist".to_string(),
+///     spa: Some(true),
+///     ..Default::default()
+/// };
+/// let app: Router = Router::new().nest_service(
+///     static_config.get_path().as_str(),
+///     StaticFiles::new(&static_config, any(handler_404)).into_method_router(),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct StaticFiles {
+    serve_dir: ServeDir<MethodRouter>,
+    cache_control: Option<HeaderValue>,
+    /// requests under this prefix skip the files and go straight to the fallback
+    api_path: Option<String>,
+    fallback: MethodRouter,
+}
+
+impl StaticFiles {
+    pub fn new(static_config: &StaticConfig, fallback: MethodRouter) -> Self {
+        let not_found = if static_config.is_spa() {
+            let index = Path::new(static_config.dir.as_str()).join(static_config.get_index());
+            spa_index(index, fallback.clone())
+        } else {
+            fallback.clone()
+        };
+        StaticFiles {
+            serve_dir: ServeDir::new(static_config.dir.as_str())
+                .append_index_html_on_directories(true)
+                .call_fallback_on_method_not_allowed(true)
+                .fallback(not_found),
+            cache_control: static_config.cache_control.as_ref().map(|cache_control| {
+                HeaderValue::from_str(cache_control)
+                    .unwrap_or_else(|_| panic!("invalid static cache_control: {}", cache_control))
+            }),
+            api_path: None,
+            fallback,
+        }
+    }
+
+    /// keep api paths (`base_path` of rest config) out of a root mount
+    pub fn api_path(mut self, api_path: Option<String>) -> Self {
+        self.api_path = api_path;
+        self
+    }
+
+    pub async fn serve(self, req: Request) -> Response {
+        if let Some(api_path) = &self.api_path {
+            let path = req.uri().path();
+            if path == api_path || path.starts_with(format!("{}/", api_path).as_str()) {
+                return self.fallback.oneshot(req).await.into_response();
+            }
+        }
+        let mut res = self.serve_dir.oneshot(req).await.into_response();
+        if let Some(cache_control) = self.cache_control {
+            if res.status().is_success() && !res.headers().contains_key(header::CACHE_CONTROL) {
+                res.headers_mut().insert(header::CACHE_CONTROL, cache_control);
+            }
+        }
+        res
+    }
+
+    pub fn into_method_router(self) -> MethodRouter {
+        any(move |req: Request| self.clone().serve(req))
+    }
+}
+
+/// serve the SPA index to html navigations, the index itself is never cached
+fn spa_index<P: AsRef<Path>>(index: P, fallback: MethodRouter) -> MethodRouter {
+    let index = ServeFile::new(index);
+    any(move |req: Request| {
+        let (index, fallback) = (index.clone(), fallback.clone());
+        async move {
+            let navigation = matches!(*req.method(), Method::GET | Method::HEAD)
+                && accept_html(req.headers());
+            if !navigation {
+                return fallback.oneshot(req).await.into_response();
+            }
+            let mut res = index.oneshot(req).await.into_response();
+            res.headers_mut()
+                .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+            res
+        }
+    })
+}
@@ -0,0 +1,20 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use axum_client_ip::{ClientIp, ClientIpSource};
+
+/// client ip resolved by `add_secure_client_ip_source_layer`, the peer address when no source is
+/// installed; `None` when the configured source (e.g. `x-real-ip`) is missing, never the proxy ip
+pub async fn client_ip(parts: &mut Parts) -> Option<IpAddr> {
+    if parts.extensions.get::<ClientIpSource>().is_none() {
+        return parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+    }
+    ClientIp::from_request_parts(parts, &())
+        .await
+        .ok()
+        .map(|ClientIp(ip)| ip)
+}
@@ -1,2 +1,3 @@
 pub mod mysql;
+pub mod page;
 pub mod postgres;
\ No newline at end of file
@@ -0,0 +1,35 @@
+use sea_orm::{ConnectionTrait, DbErr, PaginatorTrait, SelectorTrait};
+
+use crate::axum::page::{PageQuery, PagedResp};
+
+/// 分页查询
+/// Run the page query and the count query of a sea-orm `Select`
+///
+/// # Example
+/// ```rust,ignore
+/// use nano_rs_extra::axum::page::{PageQuery, PagedResp};
+/// use nano_rs_extra::sea_orm::page::fetch_page;
+/// use sea_orm::{DatabaseConnection, DbErr, EntityTrait, QueryOrder};
+///
+/// async fn list(db: &DatabaseConnection, page: &PageQuery) -> Result<PagedResp<pet::Model>, DbErr> {
+///     fetch_page(pet::Entity::find().order_by_asc(pet::Column::Id), db, page).await
+/// }
+/// ```
+pub async fn fetch_page<'db, C, S>(
+    select: S,
+    db: &'db C,
+    page: &PageQuery,
+) -> Result<PagedResp<<S::Selector as SelectorTrait>::Item>, DbErr>
+where
+    C: ConnectionTrait,
+    S: PaginatorTrait<'db, C>,
+{
+    let paginator = select.paginate(db, page.size);
+    let total = paginator.num_items().await?;
+    let items = if page.offset() < total {
+        paginator.fetch_page(page.page.saturating_sub(1)).await?
+    } else {
+        vec![]
+    };
+    Ok(PagedResp::new(items, total, page))
+}
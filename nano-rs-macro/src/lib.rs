@@ -1 +1,259 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, LitStr};
 
+/// - 业务错误码派生宏
+/// - Derive `nano_rs::axum::errors::BizError` for an error enum
+///
+/// every variant needs `#[biz_error(code = .., msg = "..")]`, `status` is optional, by default a
+/// code in 400..600 is the http status and any other code is 200, like `biz_err`.
+/// `key` is the optional i18n message key, `msg` is its fallback and the variant fields are
+/// its args (`{0}` `{1}` of tuple variants, `{name}` of named fields, they must be `Display`).
+/// the enum gets `Display` ("code: msg"), `IntoResponse` (`RestResp` with the code and msg) and
+/// `From<Self> for ServerError`, so `?` works in handlers returning `ServerError`
+///
+/// `From<Self> for ServerError` conflicts with the `From` of every `std::error::Error`
+/// (`thiserror` etc..), put `#[biz_error(skip_from)]` on those enums and raise them with
+/// `BizError::server_error`, `skip_from` also skips `Display`, the error brings its own
+///
+/// # Examples
+/// ```rust
+/// use nano_rs::axum::errors::{BizError, ServerError};
+/// use nano_rs::axum::rest::RestResp;
+/// use nano_rs::BizError;
+///
+/// #[derive(Debug, BizError)]
+/// pub enum PetError {
+///     #[biz_error(code = 40001, msg = "pet not found", status = 404)]
+///     NotFound,
+///     #[biz_error(code = 40002, msg = "pet name is taken", key = "pet.name_taken")]
+///     NameTaken(String),
+/// }
+///
+/// #[derive(Debug, thiserror::Error, BizError)]
+/// #[biz_error(skip_from)]
+/// pub enum StoreError {
+///     #[error("store closed")]
+///     #[biz_error(code = 503, msg = "store closed")]
+///     Closed,
+/// }
+///
+/// pub async fn get_pet() -> Result<RestResp<String>, ServerError> {
+///     Err(PetError::NotFound)?
+/// }
+///
+/// pub async fn get_store() -> Result<RestResp<String>, ServerError> {
+///     Err(StoreError::Closed.server_error())
+/// }
+///
+/// assert_eq!(PetError::NotFound.to_string(), "40001: pet not found");
+/// assert_eq!(PetError::NameTaken("kitty".to_string()).status(), 200);
+/// assert_eq!(StoreError::Closed.to_string(), "store closed");
+/// assert_eq!(StoreError::Closed.status(), 503);
+/// ```
+#[proc_macro_derive(BizError, attributes(biz_error))]
+pub fn derive_biz_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_biz_error(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct BizErrorVariant {
+    pattern: proc_macro2::TokenStream,
+    code: LitInt,
+    msg: LitStr,
+    status: LitInt,
+    key: Option<LitStr>,
+    /// pattern binding the fields and the i18n args built from them
+    args: (proc_macro2::TokenStream, proc_macro2::TokenStream),
+}
+
+fn expand_biz_error(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(&input, "BizError can only be derived for enums"));
+    };
+    let mut skip_from = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("biz_error")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip_from") {
+                skip_from = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip_from`"))
+            }
+        })?;
+    }
+    let mut variants = vec![];
+    for variant in data.variants.iter() {
+        let ident = &variant.ident;
+        let pattern = match &variant.fields {
+            Fields::Unit => quote!(#name::#ident),
+            Fields::Unnamed(_) => quote!(#name::#ident(..)),
+            Fields::Named(_) => quote!(#name::#ident { .. }),
+        };
+        let args = match &variant.fields {
+            Fields::Unit => (quote!(#name::#ident), quote!(vec![])),
+            Fields::Unnamed(fields) => {
+                let bindings: Vec<_> = (0..fields.unnamed.len())
+                    .map(|i| format_ident!("field_{}", i))
+                    .collect();
+                let names = (0..fields.unnamed.len()).map(|i| i.to_string());
+                (
+                    quote!(#name::#ident(#(#bindings),*)),
+                    quote!(vec![#((#names.to_string(), #bindings.to_string())),*]),
+                )
+            }
+            Fields::Named(fields) => {
+                let bindings: Vec<_> = fields.named.iter().filter_map(|field| field.ident.clone()).collect();
+                let names = bindings.iter().map(|binding| binding.to_string());
+                (
+                    quote!(#name::#ident { #(#bindings),* }),
+                    quote!(vec![#((#names.to_string(), #bindings.to_string())),*]),
+                )
+            }
+        };
+        let (mut code, mut msg, mut status, mut key) = (None, None, None, None);
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("biz_error")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("code") {
+                    code = Some(meta.value()?.parse::<LitInt>()?);
+                } else if meta.path.is_ident("msg") {
+                    msg = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.path.is_ident("status") {
+                    status = Some(meta.value()?.parse::<LitInt>()?);
+                } else if meta.path.is_ident("key") {
+                    key = Some(meta.value()?.parse::<LitStr>()?);
+                } else {
+                    return Err(meta.error("expected `code`, `msg`, `status` or `key`"));
+                }
+                Ok(())
+            })?;
+        }
+        let code = code.ok_or_else(|| {
+            Error::new_spanned(variant, "missing #[biz_error(code = ..)] on variant")
+        })?;
+        let msg = msg.ok_or_else(|| {
+            Error::new_spanned(variant, "missing #[biz_error(msg = \"..\")] on variant")
+        })?;
+        let status = match status {
+            Some(status) => status,
+            None => match code.base10_parse::<u16>() {
+                Ok(400..=599) => LitInt::new(code.base10_digits(), Span::call_site()),
+                _ => LitInt::new("200", Span::call_site()),
+            },
+        };
+        match status.base10_parse::<u16>() {
+            Ok(100..=999) => {}
+            _ => return Err(Error::new_spanned(&status, "status must be a http status code")),
+        }
+        variants.push(BizErrorVariant {
+            pattern,
+            code,
+            msg,
+            status,
+            key,
+            args,
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // an `std::error::Error` brings its own `Display` and `From` conflicts with its blanket one
+    let error_impls = if skip_from {
+        quote!()
+    } else {
+        quote! {
+            impl #impl_generics From<#name #ty_generics> for nano_rs::axum::errors::ServerError #where_clause {
+                fn from(err: #name #ty_generics) -> Self {
+                    nano_rs::axum::errors::BizError::server_error(&err)
+                }
+            }
+
+            impl #impl_generics std::fmt::Display for #name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{}: {}", nano_rs::axum::errors::BizError::code(self), nano_rs::axum::errors::BizError::msg(self))
+                }
+            }
+        }
+    };
+    let code_arms = variants.iter().map(|v| {
+        let (pattern, code) = (&v.pattern, &v.code);
+        quote!(#pattern => #code,)
+    });
+    let msg_arms = variants.iter().map(|v| {
+        let (pattern, msg) = (&v.pattern, &v.msg);
+        quote!(#pattern => #msg,)
+    });
+    let status_arms = variants.iter().map(|v| {
+        let (pattern, status) = (&v.pattern, &v.status);
+        quote!(#pattern => #status,)
+    });
+    let key_arms = variants.iter().map(|v| {
+        let pattern = &v.pattern;
+        match &v.key {
+            Some(key) => quote!(#pattern => Some(#key),),
+            None => quote!(#pattern => None,),
+        }
+    });
+    let args_arms = variants.iter().map(|v| {
+        let (pattern, args) = match &v.key {
+            Some(_) => (&v.args.0, v.args.1.clone()),
+            None => (&v.pattern, quote!(vec![])),
+        };
+        quote!(#pattern => #args,)
+    });
+    let all = variants.iter().map(|v| {
+        let (code, msg, status) = (&v.code, &v.msg, &v.status);
+        quote!(nano_rs::axum::errors::BizErrorInfo {
+            code: #code,
+            msg: #msg.to_string(),
+            status: #status,
+        })
+    });
+    Ok(quote! {
+        impl #impl_generics nano_rs::axum::errors::BizError for #name #ty_generics #where_clause {
+            fn code(&self) -> i32 {
+                match self {
+                    #(#code_arms)*
+                }
+            }
+
+            fn msg(&self) -> &'static str {
+                match self {
+                    #(#msg_arms)*
+                }
+            }
+
+            fn status(&self) -> u16 {
+                match self {
+                    #(#status_arms)*
+                }
+            }
+
+            fn key(&self) -> Option<&'static str> {
+                match self {
+                    #(#key_arms)*
+                }
+            }
+
+            fn args(&self) -> Vec<(String, String)> {
+                match self {
+                    #(#args_arms)*
+                }
+            }
+
+            fn all() -> Vec<nano_rs::axum::errors::BizErrorInfo> {
+                vec![#(#all),*]
+            }
+        }
+
+        impl #impl_generics axum::response::IntoResponse for #name #ty_generics #where_clause {
+            fn into_response(self) -> axum::response::Response {
+                axum::response::IntoResponse::into_response(nano_rs::axum::errors::BizError::info(&self))
+            }
+        }
+
+        #error_impls
+    })
+}